@@ -1,8 +1,9 @@
 #![feature(const_index, const_trait_impl)]
+#![cfg_attr(feature = "portable-simd", feature(portable_simd))]
 
 use core::hint::black_box;
 
-use criterion::{Criterion, criterion_group, criterion_main};
+use criterion::{Criterion, criterion_group};
 
 use myers_ed::prelude::*;
 
@@ -10,6 +11,16 @@ use myers_ed::prelude::*;
 const BRCA2_C50: &[u8] = &include_bytes!("brca2_c50.txt")[..512];
 const BRCA2_C50_MUT: &[u8] = &include_bytes!("brca2_c50_mut.txt")[..512];
 
+// 50000 char DNA strings, built by repeating the above. Second has an edit distance of 2 from the
+// first, all in its first 512 chars.
+fn brca2_c50_50000() -> (Vec<u8>, Vec<u8>) {
+    let a = BRCA2_C50.repeat(98)[..50_000].to_vec();
+    let mut b = a.clone();
+    b[..512].copy_from_slice(BRCA2_C50_MUT);
+
+    (a, b)
+}
+
 mod scalar {
     use super::*;
 
@@ -27,6 +38,20 @@ mod scalar {
         });
     }
 
+    pub fn bench_myers_ed_single_scalar_with_peq_8x12x12(c: &mut Criterion) {
+        let peqs: [_; 8] =
+            core::array::from_fn(|i| SingleWordPeq::<u64>::from_bytes(&BRCA2_C50[12 * i..][..12]));
+        let b: [_; 8] = core::array::from_fn(|i| &BRCA2_C50_MUT[12 * i..][..12]);
+
+        c.bench_function("bench_myers_ed_single_scalar_with_peq_8x12x12", |bch| {
+            bch.iter(|| {
+                black_box(core::array::from_fn::<_, 8, _>(|i| {
+                    myers_ed_single_scalar_with_peq(&peqs[i], b[i])
+                }))
+            })
+        });
+    }
+
     pub fn bench_myers_ed_single_scalar_64x64(c: &mut Criterion) {
         c.bench_function("bench_myers_ed_single_scalar_64x64", |b| {
             b.iter(|| {
@@ -45,11 +70,175 @@ mod scalar {
             b.iter(|| black_box(myers_ed_single_scalar_with_peq(&peq, &BRCA2_C50_MUT[..64])))
         });
     }
+
+    pub fn bench_myers_ed_single_scalar_bounded_with_peq_64x64(c: &mut Criterion) {
+        let peq = SingleWordPeq::from_bytes(&BRCA2_C50[..64]);
+
+        // An unrelated text, so the bound is exceeded long before the end of `b`.
+        let b: Vec<u8> = BRCA2_C50_MUT[..64].iter().rev().copied().collect();
+
+        c.bench_function(
+            "bench_myers_ed_single_scalar_bounded_with_peq_64x64",
+            |bch| bch.iter(|| black_box(myers_ed_single_scalar_bounded_with_peq(&peq, &b, 4))),
+        );
+    }
+
+    pub fn bench_myers_ed_single_scalar_with_peq_and_mode_prefix_64x512(c: &mut Criterion) {
+        let peq = SingleWordPeq::from_bytes(&BRCA2_C50[..64]);
+
+        c.bench_function(
+            "bench_myers_ed_single_scalar_with_peq_and_mode_prefix_64x512",
+            |b| {
+                b.iter(|| {
+                    black_box(myers_ed_single_scalar_with_peq_and_mode(
+                        &peq,
+                        BRCA2_C50_MUT,
+                        Mode::Prefix,
+                    ))
+                })
+            },
+        );
+    }
+
+    pub fn bench_myers_ed_single_scalar_align_with_peq_64x64(c: &mut Criterion) {
+        let peq = SingleWordPeq::from_bytes(&BRCA2_C50[..64]);
+
+        c.bench_function("bench_myers_ed_single_scalar_align_with_peq_64x64", |b| {
+            b.iter(|| {
+                black_box(myers_ed_single_scalar_align_with_peq(
+                    &peq,
+                    &BRCA2_C50_MUT[..64],
+                    Mode::Global,
+                ))
+            })
+        });
+    }
+
+    pub fn bench_myers_ed_single_u128_with_peq_128x128(c: &mut Criterion) {
+        let peq = SingleWordPeq::from_bytes(&BRCA2_C50[..128]);
+
+        c.bench_function("bench_myers_ed_single_u128_with_peq_128x128", |b| {
+            b.iter(|| black_box(myers_ed_single_u128_with_peq(&peq, &BRCA2_C50_MUT[..128])))
+        });
+    }
+
+    pub fn bench_myers_ed_single_wide_with_peq_256x256(c: &mut Criterion) {
+        let peq = SingleWordPeq::<[u64; 4]>::from_bytes(&BRCA2_C50[..256]);
+
+        c.bench_function("bench_myers_ed_single_wide_with_peq_256x256", |b| {
+            b.iter(|| black_box(myers_ed_single_wide_with_peq(&peq, &BRCA2_C50_MUT[..256])))
+        });
+    }
+
+    pub fn bench_single_word_peq_from_bytes_scalar_64(c: &mut Criterion) {
+        c.bench_function("bench_single_word_peq_from_bytes_scalar_64", |b| {
+            b.iter(|| black_box(SingleWordPeq::<u64>::from_bytes(&BRCA2_C50[..64])))
+        });
+    }
+
+    pub fn bench_single_word_peq_from_bytes_and_alphabet_scalar_64(c: &mut Criterion) {
+        let a = BitAlphabet::from_bytes(b"ACGT");
+
+        c.bench_function(
+            "bench_single_word_peq_from_bytes_and_alphabet_scalar_64",
+            |b| {
+                b.iter(|| {
+                    black_box(SingleWordPeq::<u64>::from_bytes_and_alphabet(
+                        &BRCA2_C50[..64],
+                        a,
+                    ))
+                })
+            },
+        );
+    }
+
+    pub fn bench_myers_ed_multi_scalar_512x512(c: &mut Criterion) {
+        c.bench_function("bench_myers_ed_multi_scalar_512x512", |b| {
+            b.iter(|| black_box(myers_ed_multi_scalar(BRCA2_C50, BRCA2_C50_MUT)))
+        });
+    }
+
+    pub fn bench_myers_ed_multi_scalar_with_peq_512x512(c: &mut Criterion) {
+        let peq = MultiWordPeq::from_bytes(BRCA2_C50);
+
+        c.bench_function("bench_myers_ed_multi_scalar_with_peq_512x512", |b| {
+            b.iter(|| black_box(myers_ed_multi_scalar_with_peq(&peq, BRCA2_C50_MUT)))
+        });
+    }
+
+    pub fn bench_myers_ed_search_scalar_with_peq_64x512(c: &mut Criterion) {
+        let peq = SingleWordPeq::from_bytes(&BRCA2_C50[200..264]);
+
+        c.bench_function("bench_myers_ed_search_scalar_with_peq_64x512", |b| {
+            b.iter(|| black_box(myers_ed_search_scalar_with_peq(&peq, BRCA2_C50_MUT, 4).count()))
+        });
+    }
+
+    pub fn bench_myers_ed_banded_scalar_512x512(c: &mut Criterion) {
+        c.bench_function("bench_myers_ed_banded_scalar_512x512", |b| {
+            b.iter(|| black_box(myers_ed_banded_scalar(BRCA2_C50, BRCA2_C50_MUT, 8)))
+        });
+    }
+
+    pub fn bench_myers_ed_banded_scalar_50000x50000(c: &mut Criterion) {
+        let (a, b) = brca2_c50_50000();
+
+        c.bench_function("bench_myers_ed_banded_scalar_50000x50000", |bch| {
+            bch.iter(|| black_box(myers_ed_banded_scalar(&a, &b, 8)))
+        });
+    }
 }
 
 use scalar::*;
 
+#[cfg(feature = "sse")]
+mod sse {
+    use super::*;
+
+    pub fn bench_myers_ed_single_sse_12x12(c: &mut Criterion) {
+        c.bench_function("bench_myers_ed_single_sse_12x12", |b| {
+            b.iter(|| black_box(myers_ed_single_sse(b"GATCAATGACTG", b"GATCAATAACTG")))
+        });
+    }
+
+    pub fn bench_myers_ed_single_sse_with_peq_128x128(c: &mut Criterion) {
+        let peq = SingleWordPeq::from_bytes(&BRCA2_C50[..128]);
+
+        c.bench_function("bench_myers_ed_single_sse_with_peq_128x128", |b| {
+            b.iter(|| black_box(myers_ed_single_sse_with_peq(&peq, &BRCA2_C50_MUT[..128])))
+        });
+    }
+}
+
+#[cfg(feature = "sse")]
+use sse::*;
+
+#[cfg(feature = "avx2")]
+mod avx2 {
+    use super::*;
+
+    pub fn bench_myers_ed_single_avx2_12x12(c: &mut Criterion) {
+        c.bench_function("bench_myers_ed_single_avx2_12x12", |b| {
+            b.iter(|| black_box(myers_ed_single_avx2(b"GATCAATGACTG", b"GATCAATAACTG")))
+        });
+    }
+
+    pub fn bench_myers_ed_single_avx2_with_peq_256x256(c: &mut Criterion) {
+        let peq = SingleWordPeq::from_bytes(&BRCA2_C50[..256]);
+
+        c.bench_function("bench_myers_ed_single_avx2_with_peq_256x256", |b| {
+            b.iter(|| black_box(myers_ed_single_avx2_with_peq(&peq, &BRCA2_C50_MUT[..256])))
+        });
+    }
+}
+
+#[cfg(feature = "avx2")]
+use avx2::*;
+
+#[cfg(feature = "avx512")]
 mod avx512 {
+    use core::arch::x86_64::__m512i;
+
     use super::*;
 
     pub fn bench_myers_ed_single_avx512_12x12(c: &mut Criterion) {
@@ -79,19 +268,392 @@ mod avx512 {
             b.iter(|| black_box(myers_ed_single_avx512_with_peq(&peq, BRCA2_C50_MUT)))
         });
     }
+
+    pub fn bench_myers_ed_single_avx512_bounded_with_peq_512x512(c: &mut Criterion) {
+        let peq = SingleWordPeq::from_bytes(BRCA2_C50);
+
+        c.bench_function(
+            "bench_myers_ed_single_avx512_bounded_with_peq_512x512",
+            |b| {
+                b.iter(|| {
+                    black_box(myers_ed_single_avx512_bounded_with_peq(
+                        &peq,
+                        BRCA2_C50_MUT,
+                        4,
+                    ))
+                })
+            },
+        );
+    }
+
+    #[cfg(feature = "avx512-epi32")]
+    pub fn bench_myers_ed_single_avx512_epi32_with_peq_512x512(c: &mut Criterion) {
+        let peq = SingleWordPeq::from_bytes(BRCA2_C50);
+
+        c.bench_function("bench_myers_ed_single_avx512_epi32_with_peq_512x512", |b| {
+            b.iter(|| black_box(myers_ed_single_avx512_epi32_with_peq(&peq, BRCA2_C50_MUT)))
+        });
+    }
+
+    pub fn bench_myers_ed_single_avx512_with_compact_peq_512x512(c: &mut Criterion) {
+        let peq = CompactPeq::from_bytes(BRCA2_C50);
+
+        c.bench_function(
+            "bench_myers_ed_single_avx512_with_compact_peq_512x512",
+            |b| b.iter(|| black_box(myers_ed_single_avx512_with_peq(&peq, BRCA2_C50_MUT))),
+        );
+    }
+
+    pub fn bench_single_word_peq_from_bytes_avx512_512(c: &mut Criterion) {
+        c.bench_function("bench_single_word_peq_from_bytes_avx512_512", |b| {
+            b.iter(|| black_box(SingleWordPeq::<__m512i>::from_bytes(BRCA2_C50)))
+        });
+    }
+
+    pub fn bench_single_word_peq_from_bytes_and_alphabet_avx512_512(c: &mut Criterion) {
+        let a = BitAlphabet::from_bytes(b"ACGT");
+
+        c.bench_function(
+            "bench_single_word_peq_from_bytes_and_alphabet_avx512_512",
+            |b| {
+                b.iter(|| {
+                    black_box(SingleWordPeq::<__m512i>::from_bytes_and_alphabet(
+                        BRCA2_C50, a,
+                    ))
+                })
+            },
+        );
+    }
+
+    pub fn bench_myers_ed_multi_avx512_512x512(c: &mut Criterion) {
+        c.bench_function("bench_myers_ed_multi_avx512_512x512", |b| {
+            b.iter(|| black_box(myers_ed_multi_avx512(BRCA2_C50, BRCA2_C50_MUT)))
+        });
+    }
+
+    pub fn bench_myers_ed_search_avx512_with_peq_256x512(c: &mut Criterion) {
+        let peq = SingleWordPeq::from_bytes(&BRCA2_C50[200..456]);
+
+        c.bench_function("bench_myers_ed_search_avx512_with_peq_256x512", |b| {
+            b.iter(|| black_box(myers_ed_search_avx512_with_peq(&peq, BRCA2_C50_MUT, 4).count()))
+        });
+    }
+
+    pub fn bench_myers_ed_banded_avx512_512x512(c: &mut Criterion) {
+        c.bench_function("bench_myers_ed_banded_avx512_512x512", |b| {
+            b.iter(|| black_box(myers_ed_banded_avx512(BRCA2_C50, BRCA2_C50_MUT, 64)))
+        });
+    }
+
+    pub fn bench_myers_ed_banded_avx512_50000x50000(c: &mut Criterion) {
+        let (a, b) = brca2_c50_50000();
+
+        c.bench_function("bench_myers_ed_banded_avx512_50000x50000", |bch| {
+            bch.iter(|| black_box(myers_ed_banded_avx512(&a, &b, 64)))
+        });
+    }
+
+    pub fn bench_myers_ed_multi_avx512_with_peq_512x512(c: &mut Criterion) {
+        let peq = MultiWordPeq::from_bytes(BRCA2_C50);
+
+        c.bench_function("bench_myers_ed_multi_avx512_with_peq_512x512", |b| {
+            b.iter(|| black_box(myers_ed_multi_avx512_with_peq(&peq, BRCA2_C50_MUT)))
+        });
+    }
+    pub fn bench_myers_ed_batch_avx512_with_peq_8x12x12(c: &mut Criterion) {
+        let peqs: [_; 8] =
+            core::array::from_fn(|i| SingleWordPeq::<u64>::from_bytes(&BRCA2_C50[12 * i..][..12]));
+        let b: [_; 8] = core::array::from_fn(|i| &BRCA2_C50_MUT[12 * i..][..12]);
+
+        c.bench_function("bench_myers_ed_batch_avx512_with_peq_8x12x12", |bch| {
+            bch.iter(|| black_box(myers_ed_batch_avx512_with_peq(peqs.each_ref(), b)))
+        });
+    }
+
+    pub fn bench_myers_ed_batch16_avx512_with_peq_16x12x12(c: &mut Criterion) {
+        let peqs: [_; 16] =
+            core::array::from_fn(|i| SingleWordPeq::<u64>::from_bytes(&BRCA2_C50[12 * i..][..12]));
+        let b: [_; 16] = core::array::from_fn(|i| &BRCA2_C50_MUT[12 * i..][..12]);
+
+        c.bench_function("bench_myers_ed_batch16_avx512_with_peq_16x12x12", |bch| {
+            bch.iter(|| black_box(myers_ed_batch16_avx512_with_peq(peqs.each_ref(), b)))
+        });
+    }
+
+    pub fn bench_myers_ed_batch_avx512_8x64x64(c: &mut Criterion) {
+        let a: [_; 8] = core::array::from_fn(|i| &BRCA2_C50[64 * i..][..64]);
+        let b: [_; 8] = core::array::from_fn(|i| &BRCA2_C50_MUT[64 * i..][..64]);
+
+        c.bench_function("bench_myers_ed_batch_avx512_8x64x64", |bch| {
+            bch.iter(|| black_box(myers_ed_batch_avx512(a, b)))
+        });
+    }
+
+    pub fn bench_myers_ed_batch_avx512_with_peq_8x64x64(c: &mut Criterion) {
+        let peqs: [_; 8] =
+            core::array::from_fn(|i| SingleWordPeq::<u64>::from_bytes(&BRCA2_C50[64 * i..][..64]));
+        let b: [_; 8] = core::array::from_fn(|i| &BRCA2_C50_MUT[64 * i..][..64]);
+
+        c.bench_function("bench_myers_ed_batch_avx512_with_peq_8x64x64", |bch| {
+            bch.iter(|| black_box(myers_ed_batch_avx512_with_peq(peqs.each_ref(), b)))
+        });
+    }
+
+    #[cfg(feature = "avx512-vbmi")]
+    pub fn bench_myers_ed_batch_avx512_with_alphabet_dna_8x64x64(c: &mut Criterion) {
+        let a: [_; 8] = core::array::from_fn(|i| &BRCA2_C50[64 * i..][..64]);
+        let b: [_; 8] = core::array::from_fn(|i| &BRCA2_C50_MUT[64 * i..][..64]);
+
+        c.bench_function(
+            "bench_myers_ed_batch_avx512_with_alphabet_dna_8x64x64",
+            |bch| {
+                bch.iter(|| black_box(myers_ed_batch_avx512_with_alphabet(a, b, BitAlphabet::DNA)))
+            },
+        );
+    }
+
+    // Same inputs and alphabet semantics as the DNA bench above, but building alphabet-aware Peqs
+    // each iteration and gathering from them, so both include their setup.
+    #[cfg(feature = "avx512-vbmi")]
+    pub fn bench_myers_ed_batch_avx512_with_alphabet_peq_dna_8x64x64(c: &mut Criterion) {
+        let a: [_; 8] = core::array::from_fn(|i| &BRCA2_C50[64 * i..][..64]);
+        let b: [_; 8] = core::array::from_fn(|i| &BRCA2_C50_MUT[64 * i..][..64]);
+
+        c.bench_function(
+            "bench_myers_ed_batch_avx512_with_alphabet_peq_dna_8x64x64",
+            |bch| {
+                bch.iter(|| {
+                    let peqs = a.map(|a| {
+                        SingleWordPeq::<u64>::from_bytes_and_alphabet(a, BitAlphabet::DNA)
+                    });
+
+                    black_box(myers_ed_batch_avx512_with_peq(peqs.each_ref(), b))
+                })
+            },
+        );
+    }
+
+    #[cfg(feature = "avx512-vbmi")]
+    pub fn bench_myers_ed_batch_avx512_with_alphabet_protein_8x64x64(c: &mut Criterion) {
+        let a: [_; 8] = core::array::from_fn(|i| &BRCA2_C50[64 * i..][..64]);
+        let b: [_; 8] = core::array::from_fn(|i| &BRCA2_C50_MUT[64 * i..][..64]);
+
+        c.bench_function(
+            "bench_myers_ed_batch_avx512_with_alphabet_protein_8x64x64",
+            |bch| {
+                bch.iter(|| {
+                    black_box(myers_ed_batch_avx512_with_alphabet(
+                        a,
+                        b,
+                        BitAlphabet::PROTEIN,
+                    ))
+                })
+            },
+        );
+    }
+
+    // 64 pairs of 12 char patterns, where every eighth text is 64 chars instead of 12.
+    fn skewed_pairs() -> Vec<(&'static [u8], &'static [u8])> {
+        (0..64)
+            .map(|i| {
+                let n = if i % 8 == 0 { 64 } else { 12 };
+
+                (&BRCA2_C50[6 * i..][..12], &BRCA2_C50_MUT[6 * i..][..n])
+            })
+            .collect()
+    }
+
+    pub fn bench_myers_ed_batch_avx512_skewed_64(c: &mut Criterion) {
+        let pairs = skewed_pairs();
+
+        c.bench_function("bench_myers_ed_batch_avx512_skewed_64", |bch| {
+            bch.iter(|| {
+                for chunk in pairs.chunks(8) {
+                    let a = core::array::from_fn(|i| chunk[i].0);
+                    let b = core::array::from_fn(|i| chunk[i].1);
+
+                    black_box(myers_ed_batch_avx512(a, b));
+                }
+            })
+        });
+    }
+
+    pub fn bench_myers_ed_batch_stream_avx512_skewed_64(c: &mut Criterion) {
+        let pairs = skewed_pairs();
+
+        c.bench_function("bench_myers_ed_batch_stream_avx512_skewed_64", |bch| {
+            bch.iter(|| {
+                for d in myers_ed_batch_stream_avx512(pairs.iter().copied()) {
+                    black_box(d);
+                }
+            })
+        });
+    }
 }
 
+#[cfg(feature = "avx512")]
 use avx512::*;
 
+#[cfg(feature = "portable-simd")]
+mod portable {
+    use super::*;
+
+    use std::simd::Simd;
+
+    pub fn bench_myers_ed_single_portable_with_peq_256x256(c: &mut Criterion) {
+        let peq = SingleWordPeq::<Simd<u64, 4>>::from_bytes(&BRCA2_C50[..256]);
+
+        c.bench_function("bench_myers_ed_single_portable_with_peq_256x256", |b| {
+            b.iter(|| {
+                black_box(myers_ed_single_portable_with_peq(
+                    &peq,
+                    &BRCA2_C50_MUT[..256],
+                ))
+            })
+        });
+    }
+
+    pub fn bench_myers_ed_single_portable_with_peq_512x512(c: &mut Criterion) {
+        let peq = SingleWordPeq::<Simd<u64, 8>>::from_bytes(BRCA2_C50);
+
+        c.bench_function("bench_myers_ed_single_portable_with_peq_512x512", |b| {
+            b.iter(|| black_box(myers_ed_single_portable_with_peq(&peq, BRCA2_C50_MUT)))
+        });
+    }
+}
+
+#[cfg(feature = "portable-simd")]
+use portable::*;
+
+mod dispatch {
+    use super::*;
+
+    pub fn bench_myers_ed_512x512(c: &mut Criterion) {
+        c.bench_function("bench_myers_ed_512x512", |b| {
+            b.iter(|| black_box(myers_ed(BRCA2_C50, BRCA2_C50_MUT)))
+        });
+    }
+
+    pub fn bench_myers_ed_with_peq_512x512(c: &mut Criterion) {
+        let peq = DispatchPeq::from_bytes(BRCA2_C50);
+
+        c.bench_function("bench_myers_ed_with_peq_512x512", |b| {
+            b.iter(|| black_box(myers_ed_with_peq(&peq, BRCA2_C50_MUT)))
+        });
+    }
+}
+
+use dispatch::*;
+
 criterion_group!(
-    benches,
+    scalar_benches,
     bench_myers_ed_single_scalar_12x12,
     bench_myers_ed_single_scalar_with_peq_12x12,
+    bench_myers_ed_single_scalar_with_peq_8x12x12,
     bench_myers_ed_single_scalar_64x64,
     bench_myers_ed_single_scalar_with_peq_64x64,
+    bench_myers_ed_single_scalar_bounded_with_peq_64x64,
+    bench_myers_ed_single_scalar_with_peq_and_mode_prefix_64x512,
+    bench_myers_ed_single_scalar_align_with_peq_64x64,
+    bench_myers_ed_single_u128_with_peq_128x128,
+    bench_myers_ed_single_wide_with_peq_256x256,
+    bench_single_word_peq_from_bytes_scalar_64,
+    bench_single_word_peq_from_bytes_and_alphabet_scalar_64,
+    bench_myers_ed_multi_scalar_512x512,
+    bench_myers_ed_multi_scalar_with_peq_512x512,
+    bench_myers_ed_search_scalar_with_peq_64x512,
+    bench_myers_ed_banded_scalar_512x512,
+    bench_myers_ed_banded_scalar_50000x50000
+);
+
+#[cfg(feature = "sse")]
+criterion_group!(
+    sse_benches,
+    bench_myers_ed_single_sse_12x12,
+    bench_myers_ed_single_sse_with_peq_128x128
+);
+
+#[cfg(feature = "avx2")]
+criterion_group!(
+    avx2_benches,
+    bench_myers_ed_single_avx2_12x12,
+    bench_myers_ed_single_avx2_with_peq_256x256
+);
+
+#[cfg(feature = "avx512")]
+criterion_group!(
+    avx512_benches,
     bench_myers_ed_single_avx512_12x12,
     bench_myers_ed_single_avx512_with_peq_12x12,
     bench_myers_ed_single_avx512_512x512,
-    bench_myers_ed_single_avx512_with_peq_512x512
+    bench_myers_ed_single_avx512_with_peq_512x512,
+    bench_myers_ed_single_avx512_bounded_with_peq_512x512,
+    bench_myers_ed_single_avx512_with_compact_peq_512x512,
+    bench_single_word_peq_from_bytes_avx512_512,
+    bench_single_word_peq_from_bytes_and_alphabet_avx512_512,
+    bench_myers_ed_multi_avx512_512x512,
+    bench_myers_ed_multi_avx512_with_peq_512x512,
+    bench_myers_ed_search_avx512_with_peq_256x512,
+    bench_myers_ed_banded_avx512_512x512,
+    bench_myers_ed_banded_avx512_50000x50000,
+    bench_myers_ed_batch_avx512_with_peq_8x12x12,
+    bench_myers_ed_batch16_avx512_with_peq_16x12x12,
+    bench_myers_ed_batch_avx512_8x64x64,
+    bench_myers_ed_batch_avx512_with_peq_8x64x64,
+    bench_myers_ed_batch_avx512_skewed_64,
+    bench_myers_ed_batch_stream_avx512_skewed_64
+);
+
+#[cfg(feature = "avx512-vbmi")]
+criterion_group!(
+    avx512_vbmi_benches,
+    bench_myers_ed_batch_avx512_with_alphabet_dna_8x64x64,
+    bench_myers_ed_batch_avx512_with_alphabet_peq_dna_8x64x64,
+    bench_myers_ed_batch_avx512_with_alphabet_protein_8x64x64
+);
+
+#[cfg(feature = "avx512-epi32")]
+criterion_group!(
+    avx512_epi32_benches,
+    bench_myers_ed_single_avx512_epi32_with_peq_512x512
+);
+
+#[cfg(feature = "portable-simd")]
+criterion_group!(
+    portable_benches,
+    bench_myers_ed_single_portable_with_peq_256x256,
+    bench_myers_ed_single_portable_with_peq_512x512
 );
-criterion_main!(benches);
+
+criterion_group!(
+    dispatch_benches,
+    bench_myers_ed_512x512,
+    bench_myers_ed_with_peq_512x512
+);
+
+// Equivalent to `criterion_main!`, but lets each backend's group be gated on its crate feature.
+fn main() {
+    scalar_benches();
+
+    #[cfg(feature = "sse")]
+    sse_benches();
+
+    #[cfg(feature = "avx2")]
+    avx2_benches();
+
+    #[cfg(feature = "avx512")]
+    avx512_benches();
+
+    #[cfg(feature = "avx512-epi32")]
+    avx512_epi32_benches();
+
+    #[cfg(feature = "avx512-vbmi")]
+    avx512_vbmi_benches();
+
+    #[cfg(feature = "portable-simd")]
+    portable_benches();
+
+    dispatch_benches();
+
+    Criterion::default().configure_from_args().final_summary();
+}
@@ -0,0 +1,17 @@
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![cfg_attr(docsrs, doc(cfg(feature = "avx2")))]
+
+use crate::assert_target_features;
+
+pub mod plumbing;
+#[cfg(feature = "avx2")]
+pub mod single;
+
+mod word;
+
+// Safety: the safe functions in this module using AVX-2 intrinsics are
+// safe because we guarantee here that avx2 is available when the avx2
+// crate feature compiles. Without the crate feature, this module is private
+// and only reached through `crate::dispatch` after avx2 is detected at
+// runtime.
+assert_target_features! { "avx2", "avx2" }
@@ -0,0 +1,202 @@
+//! Custom AVX-2 "helper intrinsics" for supporting the Myers algorithm implementation.
+
+use core::arch::x86_64::*;
+
+/// Add two `__m256i` as if they were both one-lane 256-bit integers. The operation works with
+/// 64-bit lanes, so any lane of the sum of `a` and `b` that overflows must carry a bit into the
+/// next lane. Unlike `_mm512_add_si512_custom`, every carry chain is resolved in constant time:
+/// we find the lanes that generate a carry and the lanes that would propagate one (lanes summing
+/// to exactly `0xFFFFFFFFFFFFFFFF`), then add those 4-bit masks in a general purpose register.
+///
+/// # Examples
+///
+/// ```
+/// # use core::arch::x86_64::*;
+/// # use myers_ed::avx2::plumbing::_mm256_add_si256_custom;
+/// # fn main() {
+/// // Note that memory-layout is little-endian. The carry out of lane 0 ripples through lane 1,
+/// // which sums to all ones, into lane 2.
+/// let a: __m256i = unsafe { core::mem::transmute::<[i64; 4], _>([-1, -1, 0, 0]) };
+/// let b: __m256i = unsafe { core::mem::transmute::<[i64; 4], _>([1, 0, 0, 0]) };
+///
+/// let s1: __m256i = unsafe { core::mem::transmute::<[i64; 4], _>([0, 0, 1, 0]) };
+/// let s2: __m256i = _mm256_add_si256_custom(a, b);
+/// # // __m256i doesn't implement PartialEq, so quietly transmute back to arrays.
+/// # let s1: [i64; 4] = unsafe { core::mem::transmute::<__m256i, _>(s1)};
+/// # let s2: [i64; 4] = unsafe { core::mem::transmute::<__m256i, _>(s2)};
+///
+/// assert_eq!(s1, s2);
+/// # }
+/// ```
+#[inline(always)]
+pub fn _mm256_add_si256_custom(a: __m256i, b: __m256i) -> __m256i {
+    // Safety
+    //
+    // The `avx2` `target_feature` must be available.
+    #[inline(always)]
+    unsafe fn __inner_mm256_add_si256_custom(a: __m256i, b: __m256i) -> __m256i {
+        // Add a and b together as 64-bit lanes without carry between.
+        let s = _mm256_add_epi64(a, b);
+
+        // AVX-2 only has signed 64-bit compares, so flip the sign bits to compare unsigned.
+        let sign = _mm256_set1_epi64x(i64::MIN);
+
+        // Mask of lanes generating a carry. If s < a, then we overflowed.
+        let g = _mm256_cmpgt_epi64(_mm256_xor_si256(a, sign), _mm256_xor_si256(s, sign));
+        let g = _mm256_movemask_pd(_mm256_castsi256_pd(g)) as u32;
+
+        // Mask of lanes propagating a carry. If s = 0xFFFFFFFFFFFFFFFF, an incoming carry
+        // overflows this lane too.
+        let p = _mm256_cmpeq_epi64(s, _mm256_set1_epi64x(-1_i64));
+        let p = _mm256_movemask_pd(_mm256_castsi256_pd(p)) as u32;
+
+        // Mask of lanes receiving a carry. Adding the shifted generate bits into the propagate
+        // bits ripples each carry through its run of propagating lanes, and the XOR picks out
+        // every lane whose bit was touched by a carry.
+        let c = ((g << 1) + p) ^ p;
+
+        // Spread carry mask into lanes. Lane i gets (c >> i) & 1.
+        let cb = _mm256_and_si256(
+            _mm256_srlv_epi64(_mm256_set1_epi64x(c as i64), _mm256_setr_epi64x(0, 1, 2, 3)),
+            _mm256_set1_epi64x(1),
+        );
+
+        // Add carry bits into s. The carry out of lane 3 is discarded.
+        _mm256_add_epi64(s, cb)
+    }
+
+    // Safety: we guarantee that avx2 is present when the avx2 crate feature compiles, or when reached
+    // through runtime dispatch.
+    unsafe { __inner_mm256_add_si256_custom(a, b) }
+}
+
+// Trick to evaluate a `const` context that doesn't cause a well-formedness check cycle.
+// This way the `const` context can be evaluated separately from a well-formedness proof.
+trait ConstExpr<const X: i32> {}
+impl<const X: i32> ConstExpr<X> for () {}
+
+/// Left shift all bits within a 256-bit `__m256i` type by `IMM8` bits, while shifting in zeros.
+/// `IMM8` is a `const` generic immediate and must be between `0` and `64`. `IMM8` is passed as an
+/// `i32` to match Rust's AVX-2 intrinsic ABI. See `_mm512_slli_si512_custom` for the reasoning
+/// behind the type-level trickery.
+///
+/// # Examples
+///
+/// ```
+/// # use core::arch::x86_64::*;
+/// # use myers_ed::avx2::plumbing::_mm256_slli_si256_custom;
+/// # fn main() {
+/// // Bits shifted out of the top of lane 0 are shifted into the bottom of lane 1.
+/// let a: __m256i = unsafe { core::mem::transmute::<[i64; 4], _>([i64::MIN, 0, 0, 1]) };
+/// let b: __m256i = unsafe { core::mem::transmute::<[i64; 4], _>([0, 4, 0, 8]) };
+///
+/// let s: __m256i = _mm256_slli_si256_custom::<3>(a);
+/// # // __m256i doesn't implement PartialEq, so quietly transmute back to arrays.
+/// # let b: [i64; 4] = unsafe { core::mem::transmute::<__m256i, _>(b)};
+/// # let s: [i64; 4] = unsafe { core::mem::transmute::<__m256i, _>(s)};
+///
+/// assert_eq!(b, s);
+/// # }
+/// ```
+#[inline(always)]
+#[allow(private_bounds)]
+pub fn _mm256_slli_si256_custom<const IMM8: i32>(a: __m256i) -> __m256i
+where
+    // Constrain IMM8 to be <= 64.
+    (): ConstExpr<{ 64 - IMM8 }>,
+{
+    struct Complementor;
+
+    trait ComplementShift<const N: i32, const Q: i32> {
+        // Perform the actual left shift calculation now that we are in a scope with both `N`
+        // and `Q` as available `const` generics.
+        //
+        // Safety
+        //
+        // `N` must be between 0 and 64. The `avx2` `target_feature` must be available.
+        #[inline(always)]
+        unsafe fn __inner_mm256_slli_si256_custom(a: __m256i) -> __m256i {
+            // Left shift lanes in a by N without carrying between lanes.
+            let s = _mm256_slli_epi64::<N>(a);
+
+            // Overflow bits. We right shift by Q = 64 - N to get all the bits that overflowed.
+            let o = _mm256_srli_epi64::<Q>(a);
+
+            // Shift all the overflowed bits along by 64-bits (to be in line with next lane).
+            // Permute to [o0, o0, o1, o2], then zero lane 0.
+            let m = _mm256_permute4x64_epi64::<0b10_01_00_00>(o);
+            let m = _mm256_blend_epi32::<0b0000_0011>(m, _mm256_setzero_si256());
+
+            // Fill shifted in zero bits with overflowed bits from previous lane.
+            _mm256_or_si256(s, m)
+        }
+    }
+
+    impl<const N: i32, const Q: i32> ComplementShift<N, Q> for Complementor {}
+
+    // Safety: outer function signature guarantees that `N <= 64`. We also guarantee that
+    // the avx2 target_feature is available when the avx2 crate feature compiles, or when reached
+    // through runtime dispatch.
+    unsafe {
+        <Complementor as ComplementShift<IMM8, { 64 - IMM8 }>>::__inner_mm256_slli_si256_custom(a)
+    }
+}
+
+/// Return a bitmask of the lowest `i` bits set. I.e. `dst[255:i] = 0` and `dst[(i-1):0] = 1`.
+///
+/// # Examples
+///
+/// ```
+/// # use core::arch::x86_64::*;
+/// # use myers_ed::avx2::plumbing::_mm256_mask_upto_si256_custom;
+/// # fn main() {
+/// let m1: __m256i = unsafe { core::mem::transmute::<[i64; 4], _>([-1, 255, 0, 0]) };
+///
+/// // Safety: 72 <= 256.
+/// let m2: __m256i = unsafe { _mm256_mask_upto_si256_custom(72) };
+/// # // __m256i doesn't implement PartialEq, so quietly transmute back to arrays.
+/// # let m1: [i64; 4] = unsafe { core::mem::transmute::<__m256i, _>(m1)};
+/// # let m2: [i64; 4] = unsafe { core::mem::transmute::<__m256i, _>(m2)};
+///
+/// assert_eq!(m1, m2);
+/// # }
+/// ```
+///
+/// # Safety
+///
+/// `i` must not be greater than 256. Otherwise, this is UB.
+#[inline(always)]
+pub unsafe fn _mm256_mask_upto_si256_custom(i: usize) -> __m256i {
+    let mut m = [0_u64; 4];
+
+    for (j, m) in m.iter_mut().enumerate() {
+        // Number of real bits in this lane. A full 64-bit mask would overflow the shift.
+        let bits = i.saturating_sub(64 * j).min(64);
+        *m = 1_u64.checked_shl(bits as u32).unwrap_or(0).wrapping_sub(1);
+    }
+
+    // Safety: `__m256i` has an exact underlying representation of `[u64; 4]`.
+    core::mem::transmute::<[u64; 4], __m256i>(m)
+}
+
+/// Find number of set bits in 256-bit `__m256i`. AVX-2 has no vector popcount, so this counts
+/// each 64-bit lane with scalar `popcnt`.
+///
+/// # Examples
+///
+/// ```
+/// # use core::arch::x86_64::*;
+/// # use myers_ed::avx2::plumbing::_mm256_popcnt_si256_custom;
+/// # fn main() {
+/// let a: __m256i = unsafe { core::mem::transmute::<[i64; 4], _>([1, 0, 0, 15]) };
+///
+/// assert_eq!(5, _mm256_popcnt_si256_custom(a));
+/// # }
+/// ```
+#[inline(always)]
+pub fn _mm256_popcnt_si256_custom(a: __m256i) -> i64 {
+    // Safety: `__m256i` has an exact underlying representation of `[u64; 4]`.
+    let a = unsafe { core::mem::transmute::<__m256i, [u64; 4]>(a) };
+
+    a.iter().map(|x| x.count_ones() as i64).sum()
+}
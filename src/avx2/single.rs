@@ -0,0 +1,43 @@
+use core::arch::x86_64::*;
+
+use anyhow::{Result, anyhow};
+
+use crate::generic::single::myers_ed_single_with_peq;
+use crate::peq::{Peq, SingleWordPeq};
+
+/// Perform Myers algorithm to find the edit distance between `a` and `b`. Uses SIMD AVX-2 with 256-bit words.
+/// Input bytes `a` must be `<= 256` bytes. Input bytes `b` can be any length.
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::avx2::single::myers_ed_single_avx2;
+/// # fn main() {
+/// let d = myers_ed_single_avx2(b"ACCCT", b"ACCTT");
+///
+/// assert_eq!(d, 1);
+/// # }
+/// ```
+pub fn myers_ed_single_avx2(a: &[u8], b: &[u8]) -> usize {
+    assert!(a.len() <= 256, "Input must be <= 256 bytes");
+
+    // Infallible: we've verified a.len() <= 256.
+    let peq = SingleWordPeq::from_bytes(a);
+
+    myers_ed_single_avx2_with_peq(&peq, b)
+}
+
+pub fn try_myers_ed_single_avx2(a: &[u8], b: &[u8]) -> Result<usize> {
+    if a.len() > 256 {
+        return Err(anyhow!("Input must be <= 256 bytes"));
+    }
+
+    // Infallible: we've verified a.len() <= 256.
+    let peq = SingleWordPeq::from_bytes(a);
+
+    Ok(myers_ed_single_avx2_with_peq(&peq, b))
+}
+
+pub fn myers_ed_single_avx2_with_peq<P: Peq<__m256i>>(peq: &P, b: &[u8]) -> usize {
+    myers_ed_single_with_peq(peq, b)
+}
@@ -0,0 +1,144 @@
+//! [`Word`] implementation for 256-bit AVX-2 vectors.
+
+use core::arch::x86_64::*;
+
+use crate::avx2::plumbing::*;
+use crate::word::{Unchecked, Word};
+
+// Every unsafe block below relies on avx2 being present. This holds either because the avx2
+// crate feature asserts it at compile time, or because `Unchecked<__m256i>` is only ever used
+// behind runtime detection in `crate::dispatch`.
+impl Word for Unchecked<__m256i> {
+    // Safety: `__m256i` is POD so this value is valid.
+    const ZERO: Self = Unchecked(unsafe { core::mem::zeroed() });
+
+    // Safety: `__m256i` has an exact underlying representation of `[i64; 4]`.
+    const ONE: Self = Unchecked(unsafe { core::mem::transmute::<[i64; 4], __m256i>([1, 0, 0, 0]) });
+
+    // Safety: `__m256i` has an exact underlying representation of `[i64; 4]`.
+    const ONES: Self = Unchecked(unsafe { core::mem::transmute::<[i64; 4], __m256i>([-1; 4]) });
+
+    #[inline(always)]
+    unsafe fn bit_at_unchecked(i: usize) -> Self {
+        let mut a = [0_u64; 4];
+
+        // Set bit inside selected lane.
+        *a.get_unchecked_mut(i >> 6) = 1_u64 << (i & 63);
+
+        Unchecked(core::mem::transmute::<[u64; 4], __m256i>(a))
+    }
+
+    #[inline(always)]
+    fn bit_or(self, rhs: Self) -> Self {
+        // Safety: avx2 is present, see above.
+        Unchecked(unsafe { _mm256_or_si256(self.0, rhs.0) })
+    }
+
+    #[inline(always)]
+    fn bit_and(self, rhs: Self) -> Self {
+        // Safety: avx2 is present, see above.
+        Unchecked(unsafe { _mm256_and_si256(self.0, rhs.0) })
+    }
+
+    #[inline(always)]
+    fn bit_xor(self, rhs: Self) -> Self {
+        // Safety: avx2 is present, see above.
+        Unchecked(unsafe { _mm256_xor_si256(self.0, rhs.0) })
+    }
+
+    #[inline(always)]
+    fn bit_not(self) -> Self {
+        // Safety: avx2 is present, see above.
+        Unchecked(unsafe { _mm256_xor_si256(self.0, Self::ONES.0) })
+    }
+
+    #[inline(always)]
+    fn wide_add(self, rhs: Self) -> Self {
+        Unchecked(_mm256_add_si256_custom(self.0, rhs.0))
+    }
+
+    #[inline(always)]
+    fn shl_one(self, carry: bool) -> Self {
+        let s = _mm256_slli_si256_custom::<1>(self.0);
+
+        // Safety: avx2 is present, see above.
+        Unchecked(unsafe { _mm256_or_si256(s, _mm256_setr_epi64x(carry as i64, 0, 0, 0)) })
+    }
+
+    #[inline(always)]
+    fn shr_one(self) -> Self {
+        // Safety: avx2 is present, see above.
+        unsafe {
+            // Right shift lanes by one without carrying between lanes.
+            let s = _mm256_srli_epi64::<1>(self.0);
+
+            // Move the LSB of each lane up to its MSB, then down into the previous lane.
+            // Permute to [u1, u2, u3, u3], then zero lane 3.
+            let u = _mm256_slli_epi64::<63>(self.0);
+            let m = _mm256_permute4x64_epi64::<0b11_11_10_01>(u);
+            let m = _mm256_blend_epi32::<0b1100_0000>(m, _mm256_setzero_si256());
+
+            Unchecked(_mm256_or_si256(s, m))
+        }
+    }
+
+    #[inline(always)]
+    fn msb(self) -> bool {
+        // Safety: avx2 is present, see above.
+        unsafe { _mm256_movemask_pd(_mm256_castsi256_pd(self.0)) >> 3 == 1 }
+    }
+
+    #[inline(always)]
+    unsafe fn popcnt_upto_unchecked(self, n: usize) -> usize {
+        // Safety: caller guarantees that `n <= 256`.
+        let m = _mm256_mask_upto_si256_custom(n);
+
+        _mm256_popcnt_si256_custom(_mm256_and_si256(self.0, m)) as usize
+    }
+
+    /// Eight 32x8-bit lanes, zero-padded, and a mask of the real bits.
+    type Spread = ([__m256i; 8], __m256i);
+
+    #[inline(always)]
+    unsafe fn spread_bytes_unchecked(s: &[u8]) -> ([__m256i; 8], __m256i) {
+        // Copy into a zero-padded buffer so we can always load full lanes.
+        let mut buf = [0_u8; 256];
+        buf[..s.len()].copy_from_slice(s);
+
+        let p = buf.as_ptr() as *const __m256i;
+        let lanes = core::array::from_fn(|i| _mm256_loadu_si256(p.add(i)));
+
+        // Padding bytes compare equal to symbol `0`, so remember which bits are real.
+        (lanes, _mm256_mask_upto_si256_custom(s.len()))
+    }
+
+    #[inline(always)]
+    fn eq_mask(spread: &([__m256i; 8], __m256i), c: u8) -> Self {
+        // Safety
+        //
+        // The `avx2` `target_feature` must be available.
+        #[inline(always)]
+        unsafe fn __inner_eq_mask(spread: &([__m256i; 8], __m256i), c: u8) -> __m256i {
+            let (lanes, m) = spread;
+
+            // Broadcast symbol across all 32 lanes.
+            let c = _mm256_set1_epi8(c as i8);
+
+            // Each 32-byte lane compares into exactly one 32-bit half of the result.
+            let mut eq = [0_u32; 8];
+            for (eq, &l) in eq.iter_mut().zip(lanes.iter()) {
+                *eq = _mm256_movemask_epi8(_mm256_cmpeq_epi8(l, c)) as u32;
+            }
+
+            // Safety: `__m256i` has an exact underlying representation of `[u32; 8]`.
+            _mm256_and_si256(core::mem::transmute::<[u32; 8], __m256i>(eq), *m)
+        }
+
+        // Safety: avx2 is present, see above.
+        Unchecked(unsafe { __inner_eq_mask(spread, c) })
+    }
+}
+
+// Safety: the avx2 crate feature asserts the required target features at compile time.
+#[cfg(feature = "avx2")]
+crate::macros::forward_word!(__m256i, ([__m256i; 8], __m256i));
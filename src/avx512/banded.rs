@@ -0,0 +1,50 @@
+use core::arch::x86_64::*;
+
+use anyhow::{Result, anyhow};
+
+use crate::generic::banded::myers_ed_banded;
+
+/// Perform banded Myers algorithm to find the edit distance between `a` and `b` if it is at most
+/// `k`, and `None` otherwise. Uses SIMD AVX-512 with a band of `2k + 1` diagonals in one 512-bit
+/// word, so `k` must be `<= 255`. Input bytes `a` and `b` can both be any length, and the cost is
+/// linear in them.
+///
+/// A 512-bit band costs about as much per column as the unbanded single-word kernel, so this only
+/// pays off once `a` is too long for one word. When the band fits in a `u64` or a `u128`, i.e.
+/// `k <= 31` or `k <= 63`, that narrower word is used instead, as it is faster.
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::avx512::banded::myers_ed_banded_avx512;
+/// # fn main() {
+/// let a = b"ACGT".repeat(1000);
+/// let mut b = a.clone();
+/// b.drain(1000..1100);
+///
+/// assert_eq!(myers_ed_banded_avx512(&a, &b, 100), Some(100));
+/// assert_eq!(myers_ed_banded_avx512(&a, &b, 99), None);
+/// # }
+/// ```
+pub fn myers_ed_banded_avx512(a: &[u8], b: &[u8], k: usize) -> Option<usize> {
+    assert!(k <= 255, "k must be <= 255");
+
+    myers_ed_banded_narrowest(a, b, k)
+}
+
+pub fn try_myers_ed_banded_avx512(a: &[u8], b: &[u8], k: usize) -> Result<Option<usize>> {
+    if k > 255 {
+        return Err(anyhow!("k must be <= 255"));
+    }
+
+    Ok(myers_ed_banded_narrowest(a, b, k))
+}
+
+// Run the banded kernel on the narrowest word that fits the band of `2k + 1` diagonals.
+fn myers_ed_banded_narrowest(a: &[u8], b: &[u8], k: usize) -> Option<usize> {
+    match k {
+        0..32 => myers_ed_banded::<u64>(a, b, k),
+        32..64 => myers_ed_banded::<u128>(a, b, k),
+        _ => myers_ed_banded::<__m512i>(a, b, k),
+    }
+}
@@ -0,0 +1,312 @@
+//! Portable emulation of the AVX-512 intrinsics used by [`plumbing`](crate::avx512::plumbing) and
+//! the `__m512i` [`Word`](crate::word::Word) implementation. Every operation runs on the
+//! `[u64; 8]` representation of `__m512i`, so it needs no target features at all.
+//!
+//! With the `avx512-emulated` crate feature (and without `avx512`), these stand in for the
+//! `core::arch` intrinsics of the same names. The carry rounds, shifts, masks and popcounts in
+//! `plumbing`, and the `avx512::single` and `avx512::multi` kernels built on them, then compile
+//! and run unchanged on any x86-64 machine. This makes it possible to test the kernel logic on
+//! hosts without AVX-512, at the cost of all of its speed. Runtime dispatch in `crate::dispatch`
+//! never picks [`Backend::Avx512`](crate::dispatch::Backend::Avx512) in this mode, as the emulation
+//! is far slower than the scalar backend.
+//!
+//! The tests in `tests/it/emulated.rs` check every intrinsic here against its `core::arch`
+//! counterpart when the host supports AVX-512, and the kernels built on them against a naive DP.
+
+// Every function here is safe to call. They are `unsafe` only so that they can stand in for the
+// intrinsics they emulate at the same call sites, which wrap them in `unsafe` blocks.
+#![allow(clippy::missing_safety_doc)]
+
+pub use core::arch::x86_64::{__m512i, __mmask8, __mmask64, _MM_CMPINT_ENUM, _MM_CMPINT_LT};
+
+#[inline(always)]
+fn lanes(a: __m512i) -> [u64; 8] {
+    // Safety: `__m512i` has an exact underlying representation of `[u64; 8]`.
+    unsafe { core::mem::transmute::<__m512i, [u64; 8]>(a) }
+}
+
+#[inline(always)]
+fn from_lanes(a: [u64; 8]) -> __m512i {
+    // Safety: `__m512i` has an exact underlying representation of `[u64; 8]`.
+    unsafe { core::mem::transmute::<[u64; 8], __m512i>(a) }
+}
+
+#[inline(always)]
+fn map(a: __m512i, f: impl Fn(u64) -> u64) -> __m512i {
+    from_lanes(lanes(a).map(f))
+}
+
+#[inline(always)]
+fn zip(a: __m512i, b: __m512i, f: impl Fn(u64, u64) -> u64) -> __m512i {
+    let (a, b) = (lanes(a), lanes(b));
+
+    from_lanes(core::array::from_fn(|i| f(a[i], b[i])))
+}
+
+#[inline(always)]
+fn blend(src: __m512i, k: __mmask8, a: __m512i) -> __m512i {
+    let (src, a) = (lanes(src), lanes(a));
+
+    from_lanes(core::array::from_fn(|i| {
+        if k >> i & 1 == 1 { a[i] } else { src[i] }
+    }))
+}
+
+#[inline(always)]
+fn cmp_mask(a: __m512i, b: __m512i, f: impl Fn(u64, u64) -> bool) -> __mmask8 {
+    let (a, b) = (lanes(a), lanes(b));
+
+    (0..8).fold(0, |k, i| k | (f(a[i], b[i]) as u8) << i)
+}
+
+/// Return a vector with all bits cleared.
+#[inline(always)]
+pub unsafe fn _mm512_setzero_si512() -> __m512i {
+    from_lanes([0; 8])
+}
+
+/// Broadcast `a` to all 64-bit lanes.
+#[inline(always)]
+pub unsafe fn _mm512_set1_epi64(a: i64) -> __m512i {
+    from_lanes([a as u64; 8])
+}
+
+/// Broadcast `a` to all 8-bit lanes.
+#[inline(always)]
+pub unsafe fn _mm512_set1_epi8(a: i8) -> __m512i {
+    from_lanes([u64::from_ne_bytes([a as u8; 8]); 8])
+}
+
+/// Broadcast `a` to the 64-bit lanes selected by `k`, and copy the others from `src`.
+#[inline(always)]
+pub unsafe fn _mm512_mask_set1_epi64(src: __m512i, k: __mmask8, a: i64) -> __m512i {
+    blend(src, k, _mm512_set1_epi64(a))
+}
+
+/// Broadcast `a` to the 64-bit lanes selected by `k`, and zero the others.
+#[inline(always)]
+pub unsafe fn _mm512_maskz_set1_epi64(k: __mmask8, a: i64) -> __m512i {
+    blend(_mm512_setzero_si512(), k, _mm512_set1_epi64(a))
+}
+
+/// Add 64-bit lanes, wrapping on overflow.
+#[inline(always)]
+pub unsafe fn _mm512_add_epi64(a: __m512i, b: __m512i) -> __m512i {
+    zip(a, b, u64::wrapping_add)
+}
+
+/// Subtract 64-bit lanes in `b` from `a` in the lanes selected by `k`, and copy the others from
+/// `src`.
+#[inline(always)]
+pub unsafe fn _mm512_mask_sub_epi64(src: __m512i, k: __mmask8, a: __m512i, b: __m512i) -> __m512i {
+    blend(src, k, zip(a, b, u64::wrapping_sub))
+}
+
+/// Shift 64-bit lanes left by `IMM8`, shifting in zeros.
+#[inline(always)]
+pub unsafe fn _mm512_slli_epi64<const IMM8: u32>(a: __m512i) -> __m512i {
+    map(a, |a| a.checked_shl(IMM8).unwrap_or(0))
+}
+
+/// Shift 64-bit lanes right by `IMM8`, shifting in zeros.
+#[inline(always)]
+pub unsafe fn _mm512_srli_epi64<const IMM8: u32>(a: __m512i) -> __m512i {
+    map(a, |a| a.checked_shr(IMM8).unwrap_or(0))
+}
+
+/// Shift 64-bit lanes in `a` left by the matching lanes of `count`, shifting in zeros.
+#[inline(always)]
+pub unsafe fn _mm512_sllv_epi64(a: __m512i, count: __m512i) -> __m512i {
+    zip(a, count, |a, n| {
+        a.checked_shl(n.min(64) as u32).unwrap_or(0)
+    })
+}
+
+/// Shift 64-bit lanes in `a` right by the matching lanes of `count`, shifting in zeros.
+#[inline(always)]
+pub unsafe fn _mm512_srlv_epi64(a: __m512i, count: __m512i) -> __m512i {
+    zip(a, count, |a, n| {
+        a.checked_shr(n.min(64) as u32).unwrap_or(0)
+    })
+}
+
+/// Move lane `idx[i] % 8` of `a` into lane `i` in the lanes selected by `k`, and zero the others.
+#[inline(always)]
+pub unsafe fn _mm512_maskz_permutexvar_epi64(k: __mmask8, idx: __m512i, a: __m512i) -> __m512i {
+    let (idx, a) = (lanes(idx), lanes(a));
+
+    blend(
+        _mm512_setzero_si512(),
+        k,
+        from_lanes(idx.map(|i| a[(i & 7) as usize])),
+    )
+}
+
+/// Concatenate `a:b` into 16 64-bit lanes, shift right by `IMM8 % 8` lanes and return the low 8.
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::avx512::emulated::*;
+/// # fn main() {
+/// let a: __m512i = unsafe { core::mem::transmute::<[u64; 8], _>([8, 9, 10, 11, 12, 13, 14, 15]) };
+/// let b: __m512i = unsafe { core::mem::transmute::<[u64; 8], _>([0, 1, 2, 3, 4, 5, 6, 7]) };
+///
+/// let s: [u64; 8] = unsafe { core::mem::transmute(_mm512_alignr_epi64::<7>(a, b)) };
+/// assert_eq!(s, [7, 8, 9, 10, 11, 12, 13, 14]);
+/// # }
+/// ```
+#[inline(always)]
+pub unsafe fn _mm512_alignr_epi64<const IMM8: i32>(a: __m512i, b: __m512i) -> __m512i {
+    let (a, b) = (lanes(a), lanes(b));
+    let n = (IMM8 & 7) as usize;
+
+    from_lanes(core::array::from_fn(|i| {
+        if i + n < 8 { b[i + n] } else { a[i + n - 8] }
+    }))
+}
+
+/// Bitwise OR.
+#[inline(always)]
+pub unsafe fn _mm512_or_si512(a: __m512i, b: __m512i) -> __m512i {
+    zip(a, b, |a, b| a | b)
+}
+
+/// Bitwise AND.
+#[inline(always)]
+pub unsafe fn _mm512_and_si512(a: __m512i, b: __m512i) -> __m512i {
+    zip(a, b, |a, b| a & b)
+}
+
+/// Bitwise XOR.
+#[inline(always)]
+pub unsafe fn _mm512_xor_si512(a: __m512i, b: __m512i) -> __m512i {
+    zip(a, b, |a, b| a ^ b)
+}
+
+/// Bitwise OR of 64-bit lanes in the lanes selected by `k`, and copy the others from `src`.
+#[inline(always)]
+pub unsafe fn _mm512_mask_or_epi64(src: __m512i, k: __mmask8, a: __m512i, b: __m512i) -> __m512i {
+    blend(src, k, _mm512_or_si512(a, b))
+}
+
+/// Apply the three-input boolean function with truth table `IMM8` to each bit of `a`, `b` and
+/// `c`. Bit `(a << 2) | (b << 1) | c` of `IMM8` is the result for those input bits.
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::avx512::emulated::*;
+/// # fn main() {
+/// let a: __m512i = unsafe { core::mem::transmute::<[u64; 8], _>([0xF0; 8]) };
+/// let b: __m512i = unsafe { core::mem::transmute::<[u64; 8], _>([0xCC; 8]) };
+/// let c: __m512i = unsafe { core::mem::transmute::<[u64; 8], _>([0xAA; 8]) };
+///
+/// // The truth table of a function is the function applied to these three inputs.
+/// let not: [u64; 8] = unsafe { core::mem::transmute(_mm512_ternarylogic_epi64::<0x55>(c, c, c)) };
+/// let maj: [u64; 8] = unsafe { core::mem::transmute(_mm512_ternarylogic_epi64::<0xE8>(a, b, c)) };
+/// let xor: [u64; 8] = unsafe { core::mem::transmute(_mm512_ternarylogic_epi64::<0x96>(a, b, c)) };
+///
+/// assert_eq!(not, [!0xAA; 8]);
+/// assert_eq!(maj, [0xE8; 8]);
+/// assert_eq!(xor, [0x96; 8]);
+/// # }
+/// ```
+#[inline(always)]
+pub unsafe fn _mm512_ternarylogic_epi64<const IMM8: i32>(
+    a: __m512i,
+    b: __m512i,
+    c: __m512i,
+) -> __m512i {
+    let (a, b, c) = (lanes(a), lanes(b), lanes(c));
+
+    from_lanes(core::array::from_fn(|i| {
+        // OR together the minterms of the truth table.
+        (0..8).filter(|t| IMM8 >> t & 1 == 1).fold(0, |r, t| {
+            let a = if t & 4 != 0 { a[i] } else { !a[i] };
+            let b = if t & 2 != 0 { b[i] } else { !b[i] };
+            let c = if t & 1 != 0 { c[i] } else { !c[i] };
+
+            r | (a & b & c)
+        })
+    }))
+}
+
+/// Compare unsigned 64-bit lanes with the predicate `IMM3`, one of the `_MM_CMPINT_*` constants.
+#[inline(always)]
+pub unsafe fn _mm512_cmp_epu64_mask<const IMM3: _MM_CMPINT_ENUM>(
+    a: __m512i,
+    b: __m512i,
+) -> __mmask8 {
+    cmp_mask(a, b, |a, b| match IMM3 & 7 {
+        0 => a == b,
+        1 => a < b,
+        2 => a <= b,
+        3 => false,
+        4 => a != b,
+        5 => a >= b,
+        6 => a > b,
+        _ => true,
+    })
+}
+
+/// Test 64-bit lanes of `a & b` for any set bits.
+#[inline(always)]
+pub unsafe fn _mm512_test_epi64_mask(a: __m512i, b: __m512i) -> __mmask8 {
+    cmp_mask(a, b, |a, b| a & b != 0)
+}
+
+/// Compare 64-bit lanes for equality.
+#[inline(always)]
+pub unsafe fn _mm512_cmpeq_epi64_mask(a: __m512i, b: __m512i) -> __mmask8 {
+    cmp_mask(a, b, |a, b| a == b)
+}
+
+/// Compare signed 64-bit lanes for less-than.
+#[inline(always)]
+pub unsafe fn _mm512_cmplt_epi64_mask(a: __m512i, b: __m512i) -> __mmask8 {
+    cmp_mask(a, b, |a, b| (a as i64) < (b as i64))
+}
+
+/// Count the set bits of each 64-bit lane.
+#[inline(always)]
+pub unsafe fn _mm512_popcnt_epi64(a: __m512i) -> __m512i {
+    map(a, |a| a.count_ones() as u64)
+}
+
+/// Sum the 64-bit lanes, wrapping on overflow.
+#[inline(always)]
+pub unsafe fn _mm512_reduce_add_epi64(a: __m512i) -> i64 {
+    lanes(a).into_iter().fold(0, u64::wrapping_add) as i64
+}
+
+/// Load the 8-bit lanes selected by `k` from `mem_addr`, and zero the others. Masked-out bytes
+/// are never read.
+///
+/// # Safety
+///
+/// Every byte selected by `k` must be valid to read.
+#[inline(always)]
+pub unsafe fn _mm512_maskz_loadu_epi8(k: __mmask64, mem_addr: *const i8) -> __m512i {
+    let mut bytes = [0_u8; 64];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        if k >> i & 1 == 1 {
+            *byte = *mem_addr.add(i) as u8;
+        }
+    }
+
+    // Safety: `__m512i` has an exact underlying representation of `[u8; 64]`.
+    core::mem::transmute::<[u8; 64], __m512i>(bytes)
+}
+
+/// Compare 8-bit lanes for equality in the lanes selected by `k`. Other lanes compare unequal.
+#[inline(always)]
+pub unsafe fn _mm512_mask_cmpeq_epi8_mask(k: __mmask64, a: __m512i, b: __m512i) -> __mmask64 {
+    // Safety: `__m512i` has an exact underlying representation of `[u8; 64]`.
+    let (a, b) = (
+        core::mem::transmute::<__m512i, [u8; 64]>(a),
+        core::mem::transmute::<__m512i, [u8; 64]>(b),
+    );
+
+    (0..64).fold(0, |m, i| m | ((a[i] == b[i]) as u64) << i) & k
+}
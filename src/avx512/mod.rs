@@ -1,14 +1,42 @@
-#![cfg(feature = "avx512")]
 #![cfg_attr(docsrs, feature(doc_cfg))]
-#![cfg_attr(docsrs, doc(cfg(feature = "avx512")))]
+#![cfg_attr(docsrs, doc(cfg(any(feature = "avx512", feature = "avx512-emulated"))))]
 
 use crate::assert_target_features;
 
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
+pub mod banded;
+#[cfg(feature = "avx512")]
+pub mod batch;
+#[cfg(feature = "avx512-emulated")]
+pub mod emulated;
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
 pub mod multi;
 pub mod plumbing;
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
+pub mod search;
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
 pub mod single;
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
+pub mod u512;
+
+mod word;
+
+// The intrinsics behind `plumbing` and `word`. The avx512-emulated crate feature swaps them for
+// portable `[u64; 8]` versions, unless the avx512 crate feature asks for the real ones.
+#[cfg(not(all(feature = "avx512-emulated", not(feature = "avx512"))))]
+use core::arch::x86_64 as arch;
+#[cfg(all(feature = "avx512-emulated", not(feature = "avx512")))]
+use emulated as arch;
 
 // Safety: the safe functions in this module using AVX-512 intrinsics are
-// safe because we guarantee here that avx512f and avx512vpopcntdq are
-// available when the avx512 crate feature compiles.
-assert_target_features! { "avx512", "avx512f", "avx512vpopcntdq" }
+// safe because we guarantee here that avx512f, avx512bw and avx512vpopcntdq
+// are available when the avx512 crate feature compiles. Without the crate
+// feature, this module is private and only reached through `crate::dispatch`
+// after those target features are detected at runtime.
+assert_target_features! { "avx512", "avx512f", "avx512bw", "avx512vpopcntdq" }
+
+// Safety: the 32-bit lane main loop additionally uses AVX-512 VBMI2 funnel shifts.
+assert_target_features! { "avx512-epi32", "avx512f", "avx512bw", "avx512vbmi2", "avx512vpopcntdq" }
+
+// Safety: the small alphabet batch kernel additionally uses AVX-512 VBMI byte permutes.
+assert_target_features! { "avx512-vbmi", "avx512f", "avx512bw", "avx512vbmi", "avx512vpopcntdq" }
@@ -1,8 +1,6 @@
 //! Custom AVX-512 "helper intrinsics" for supporting the Myers algorithm implementation.
 
-use core::arch::x86_64::*;
-
-use crate::peq::Word;
+use crate::avx512::arch::*;
 
 /// Add two `__m512i` as if they were both one-lane 512-bit integers. Accepts a
 /// compile-time hint about the number of expected carries between lanes there
@@ -18,17 +16,16 @@ use crate::peq::Word;
 /// # Examples
 ///
 /// ```
-/// # use core::arch::x86_64::*;
 /// # use myers_ed::avx512::plumbing::_mm512_add_si512_custom;
+/// # use myers_ed::avx512::u512::U512;
 /// # fn main() {
-/// let a: __m512i = unsafe { core::mem::transmute::<[i64; 8], _>([1, 0, 0, 0, 0, 0, 0, 1]) };
-/// let b: __m512i = unsafe { core::mem::transmute::<[i64; 8], _>([1, 0, 0, 0, 0, 0, 0, -1]) };
+/// // Note that memory-layout is little-endian, so the carry out of lane 0 goes into lane 1
+/// // and the carry out of lane 7 is discarded.
+/// let a = U512::from([u64::MAX, 0, 0, 0, 0, 0, 0, 1]);
+/// let b = U512::from([1, 0, 0, 0, 0, 0, 0, u64::MAX]);
 ///
-/// let s1: __m512i = unsafe { core::mem::transmute::<[i64; 8], _>([2, 0, 0, 0, 0, 0, 1, 0]) };
-/// let s2: __m512i = _mm512_add_si512_custom::<1>(a, b);
-/// # // __m512i doesn't implement PartialEq, so quietly transmute back to arrays.
-/// # let s1: [i64; 8] = unsafe { core::mem::transmute::<__m512i, _>(s1)};
-/// # let s2: [i64; 8] = unsafe { core::mem::transmute::<__m512i, _>(s2)};
+/// let s1 = U512::from([0, 1, 0, 0, 0, 0, 0, 0]);
+/// let s2 = U512::from(_mm512_add_si512_custom::<1>(a.into(), b.into()));
 ///
 /// assert_eq!(s1, s2);
 /// # }
@@ -56,10 +53,10 @@ pub fn _mm512_add_si512_custom<const LIKELY_CARRY_ROUNDS: u32>(a: __m512i, b: __
         // will omit the loop entirely.
         for _ in 0..LIKELY_CARRY_ROUNDS {
             // Mask of carry bits. If s < a, then we overflowed and need a carry bit.
-            cm = _mm512_cmp_epu64_mask(s, a, _MM_CMPINT_LT);
+            cm = _mm512_cmp_epu64_mask::<_MM_CMPINT_LT>(s, a);
 
-            // Broadcast carry bits across lanes. Right shift mask to propagate bits up along lanes.
-            let cb = _mm512_maskz_set1_epi64(cm >> 1, 1_i64);
+            // Broadcast carry bits across lanes. Left shift mask to propagate bits up along lanes.
+            let cb = _mm512_maskz_set1_epi64(cm << 1, 1_i64);
 
             // Save current pre-carry lanes.
             a = s;
@@ -72,7 +69,7 @@ pub fn _mm512_add_si512_custom<const LIKELY_CARRY_ROUNDS: u32>(a: __m512i, b: __
         // If `LIKELY_CARRY_ROUNDS` was a good hint, then we hope to return immediately.
         loop {
             // Check if we had any more overflows and need to do more carry logic.
-            cm = _mm512_cmp_epu64_mask(s, a, _MM_CMPINT_LT);
+            cm = _mm512_cmp_epu64_mask::<_MM_CMPINT_LT>(s, a);
 
             // It is extremely unlikely, but not impossible, that we need another carry round.
             // Assume that s = a + b overflows ==> s = a + b - 2^64. Now, s + 1 = a + b - 2^64 + 1.
@@ -100,8 +97,8 @@ pub fn _mm512_add_si512_custom<const LIKELY_CARRY_ROUNDS: u32>(a: __m512i, b: __
             // If the `LIKELY_CARRY_ROUNDS` hint was bad, and we still have carries to
             // propagate, then continue propagating.
 
-            // Broadcast carry bits across lanes. Right shift mask to propagate bits up along lanes.
-            let cb = _mm512_maskz_set1_epi64(cm >> 1, 1_i64);
+            // Broadcast carry bits across lanes. Left shift mask to propagate bits up along lanes.
+            let cb = _mm512_maskz_set1_epi64(cm << 1, 1_i64);
 
             // Save current pre-carry lanes.
             a = s;
@@ -111,10 +108,66 @@ pub fn _mm512_add_si512_custom<const LIKELY_CARRY_ROUNDS: u32>(a: __m512i, b: __
         }
     }
 
-    // Safety: We guarantee that the avx512f target_feature is available when the avx512 crate feature compiles.
+    // Safety: we guarantee that avx512f is present when the avx512 crate feature compiles, or when
+    // reached through runtime dispatch.
     unsafe { __inner_mm512_add_si512_custom::<LIKELY_CARRY_ROUNDS>(a, b) }
 }
 
+/// Add two `__m512i` as if they were both one-lane 512-bit integers, resolving every carry chain in
+/// constant time. Unlike `_mm512_add_si512_custom`, there are no carry rounds and no branches. We
+/// find the lanes that generate a carry and the lanes that would propagate one (lanes summing to
+/// exactly `0xFFFFFFFFFFFFFFFF`), then add those 8-bit masks in a general purpose register. This
+/// makes worst-case inputs, such as an all-ones VP in the first iteration, as fast as any other.
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::avx512::plumbing::_mm512_add_si512_lookahead_custom;
+/// # use myers_ed::avx512::u512::U512;
+/// # fn main() {
+/// // Note that memory-layout is little-endian. The carry out of lane 0 ripples through lanes 1 to
+/// // 6, which all sum to all ones, into lane 7.
+/// let a = U512::MAX;
+/// let b = U512::ONE;
+///
+/// let s1 = U512::ZERO;
+/// let s2 = U512::from(_mm512_add_si512_lookahead_custom(a.into(), b.into()));
+///
+/// assert_eq!(s1, s2);
+/// # }
+/// ```
+#[inline(always)]
+pub fn _mm512_add_si512_lookahead_custom(a: __m512i, b: __m512i) -> __m512i {
+    // Safety
+    //
+    // The `avx512f` `target_feature` must be available.
+    #[inline(always)]
+    unsafe fn __inner_mm512_add_si512_lookahead_custom(a: __m512i, b: __m512i) -> __m512i {
+        // Add a and b together as 64-bit lanes without carry between.
+        let s = _mm512_add_epi64(a, b);
+
+        // Mask of lanes generating a carry. If s < a, then we overflowed.
+        let g = _mm512_cmp_epu64_mask::<_MM_CMPINT_LT>(s, a);
+
+        // Mask of lanes propagating a carry. If s = 0xFFFFFFFFFFFFFFFF, an incoming carry
+        // overflows this lane too.
+        let p = _mm512_cmpeq_epi64_mask(s, _mm512_set1_epi64(-1_i64));
+
+        // Mask of lanes receiving a carry. Adding the shifted generate bits into the propagate
+        // bits ripples each carry through its run of propagating lanes, and the XOR picks out
+        // every lane whose bit was touched by a carry. The carry out of lane 7 falls off the
+        // top of the 8-bit mask and is discarded.
+        let c = (g << 1).wrapping_add(p) ^ p;
+
+        // Add carry bits into s. Subtracting -1 saves materialising a vector of ones.
+        _mm512_mask_sub_epi64(s, c, s, _mm512_set1_epi64(-1_i64))
+    }
+
+    // Safety: we guarantee that avx512f is present when the avx512 crate feature compiles, or when
+    // reached through runtime dispatch.
+    unsafe { __inner_mm512_add_si512_lookahead_custom(a, b) }
+}
+
 // Trick to evaluate a `const` context that doesn't cause a well-formedness check cycle.
 // This way the `const` context can be evaluated separately from a well-formedness proof.
 trait ConstExpr<const X: u32> {}
@@ -129,18 +182,14 @@ impl<const X: u32> ConstExpr<X> for () {}
 /// # Examples
 ///
 /// ```
-/// # use core::arch::x86_64::*;
 /// # use myers_ed::avx512::plumbing::_mm512_slli_si512_custom;
+/// # use myers_ed::avx512::u512::U512;
 /// # fn main() {
-/// let a: __m512i = unsafe { core::mem::transmute::<[i64; 8], _>([0, 0, 0, 0, 0, 0, 0, 1]) };
-/// let b: __m512i = unsafe { core::mem::transmute::<[i64; 8], _>([0, 0, 0, 0, 0, 0, 0, 8]) };
+/// // Bits shifted out of the top of lane 0 are shifted into the bottom of lane 1.
+/// let a = U512::from([1 << 63, 0, 0, 0, 0, 0, 0, 1]);
+/// let b = U512::from([0, 4, 0, 0, 0, 0, 0, 8]);
 ///
-/// let s: __m512i = _mm512_slli_si512_custom::<3>(a);
-/// # // __m512i doesn't implement PartialEq, so quietly transmute back to arrays.
-/// # let b: [i64; 8] = unsafe { core::mem::transmute::<__m512i, _>(b)};
-/// # let s: [i64; 8] = unsafe { core::mem::transmute::<__m512i, _>(s)};
-///
-/// assert_eq!(b, s);
+/// assert_eq!(b, U512::from(_mm512_slli_si512_custom::<3>(a.into())));
 /// # }
 /// ```
 #[inline(always)]
@@ -174,13 +223,14 @@ where
         #[inline(always)]
         unsafe fn __inner_mm512_slli_si512_custom(a: __m512i) -> __m512i {
             // Left shift lanes in a by N without carrying between lanes.
-            let s = _mm512_slli_epi64(a, N);
+            let s = _mm512_slli_epi64::<N>(a);
 
             // Overflow bits. We right shift by Q = 64 - N to get all the bits that overflowed.
-            let o = _mm512_srli_epi64(a, Q);
+            let o = _mm512_srli_epi64::<Q>(a);
 
-            // Shift all the overflowed bits along by 64-bits (to be in line with next lane).
-            let m = _mm512_alignr_epi64(__m512i::ZERO, o, 1);
+            // Shift all the overflowed bits along by 64-bits (to be in line with next lane). This
+            // is a lane-wise rotate of `o:0` by 7 lanes, so dst[i] = o[i - 1] and dst[0] = 0.
+            let m = _mm512_alignr_epi64::<7>(o, _mm512_setzero_si512());
 
             // Fill shifted in zero bits with overflowed bits from previous lane. Since we're
             // adding into zero bits, OR and ADD are the same, and we use the logical op ports
@@ -192,28 +242,85 @@ where
     impl<const N: u32, const Q: u32> ComplementShift<N, Q> for Complementor {}
 
     // Safety: outer function signature guarantees that `N < 64`. We also guarantee that
-    // the avx512f target_feature is available when the avx512 crate feature compiles.
+    // the avx512f target_feature is available when the avx512 crate feature compiles, or when
+    // reached through runtime dispatch.
     unsafe {
         <Complementor as ComplementShift<IMM8, { 64 - IMM8 }>>::__inner_mm512_slli_si512_custom(a)
     }
 }
 
+/// Right shift all bits within a 512-bit `__m512i` type by `IMM8` bits, while shifting in zeros.
+/// `IMM8` is a `const` generic immediate and must be less than or equal to `64`, with the same
+/// caveats as `_mm512_slli_si512_custom`.
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::avx512::plumbing::_mm512_srli_si512_custom;
+/// # use myers_ed::avx512::u512::U512;
+/// # fn main() {
+/// // Bits shifted out of the bottom of lane 1 are shifted into the top of lane 0.
+/// let a = U512::from([0, 4, 0, 0, 0, 0, 0, 8]);
+/// let b = U512::from([1 << 63, 0, 0, 0, 0, 0, 0, 1]);
+///
+/// assert_eq!(b, U512::from(_mm512_srli_si512_custom::<3>(a.into())));
+/// # }
+/// ```
+#[inline(always)]
+#[allow(private_bounds)]
+pub fn _mm512_srli_si512_custom<const IMM8: u32>(a: __m512i) -> __m512i
+where
+    // Constrain IMM8 to be <= 64, see `_mm512_slli_si512_custom`.
+    (): ConstExpr<{ 64 - IMM8 }>,
+{
+    // The same `Complementor` setup as `_mm512_slli_si512_custom`, to get both `N` and
+    // `Q = 64 - N` as `const` generics for the immediate shifts.
+    struct Complementor;
+
+    trait ComplementShift<const N: u32, const Q: u32> {
+        // Safety
+        //
+        // `N` must be less than or equal to 64. The `avx512f` `target_feature` must be available.
+        #[inline(always)]
+        unsafe fn __inner_mm512_srli_si512_custom(a: __m512i) -> __m512i {
+            // Right shift lanes in a by N without carrying between lanes.
+            let s = _mm512_srli_epi64::<N>(a);
+
+            // Underflow bits. We left shift by Q = 64 - N to get all the bits that underflowed.
+            let o = _mm512_slli_epi64::<Q>(a);
+
+            // Shift all the underflowed bits down by 64-bits (to be in line with previous lane).
+            // This is a lane-wise rotate of `0:o` by 1 lane, so dst[i] = o[i + 1] and dst[7] = 0.
+            let m = _mm512_alignr_epi64::<1>(_mm512_setzero_si512(), o);
+
+            // Fill shifted in zero bits with underflowed bits from next lane.
+            _mm512_add_epi64(s, m)
+        }
+    }
+
+    impl<const N: u32, const Q: u32> ComplementShift<N, Q> for Complementor {}
+
+    // Safety: outer function signature guarantees that `N <= 64`. We also guarantee that
+    // the avx512f target_feature is available when the avx512 crate feature compiles, or when
+    // reached through runtime dispatch.
+    unsafe {
+        <Complementor as ComplementShift<IMM8, { 64 - IMM8 }>>::__inner_mm512_srli_si512_custom(a)
+    }
+}
+
 /// Return a bitmask of set bits up to bit `i`. I.e. `dst[511:(i+1)] = 0` and `dst[i:0] = 1`.
 ///
 /// # Examples
 ///
 /// ```
-/// # use core::arch::x86_64::*;
 /// # use myers_ed::avx512::plumbing::_mm512_mask_upto_si512_custom;
+/// # use myers_ed::avx512::u512::U512;
 /// # fn main() {
 /// // Note that memory-layout is little-endian.
-/// let m1: __m512i = unsafe { core::mem::transmute::<[i64; 8], _>([255, 0, 0, 0, 0, 0, 0, 0]) };
+/// let m1 = U512::from([255, 0, 0, 0, 0, 0, 0, 0]);
 ///
 /// // Safety: 8 < 512.
-/// let m2: __m512i = unsafe { _mm512_mask_upto_si512_custom(8) };
-/// # // __m512i doesn't implement PartialEq, so quietly transmute back to arrays.
-/// # let m1: [i64; 8] = unsafe { core::mem::transmute::<__m512i, _>(m1)};
-/// # let m2: [i64; 8] = unsafe { core::mem::transmute::<__m512i, _>(m2)};
+/// let m2 = U512::from(unsafe { _mm512_mask_upto_si512_custom(8) });
 ///
 /// assert_eq!(m1, m2);
 /// # }
@@ -224,17 +331,19 @@ where
 /// `i` must not be greater than 512. Otherwise, this is UB.
 #[inline(always)]
 pub unsafe fn _mm512_mask_upto_si512_custom(i: usize) -> __m512i {
-    // Lane containing intended MSB. 1_u8 << (i / 64).
-    let lane = 1_u8 << (i >> 6);
+    // Lane containing intended MSB. 1_u16 << (i / 64). Computed in 16 bits so that `i = 512`
+    // selects the (non-existent) ninth lane instead of overflowing.
+    let lane = 1_u16 << (i >> 6);
 
-    // Mask of lanes before lane that contains intended MSB. max(1_u8 << (i / 64) - 1_u8, 0).
-    let low_lanes = lane.saturating_sub(1);
+    // Mask of lanes before lane that contains intended MSB. (1_u16 << (i / 64)) - 1_u16.
+    let low_lanes = (lane - 1) as u8;
+    let lane = lane as u8;
 
     // Start with dst[511:0] = 0 and set dst[((i / 64) * 64) - 1:0] = 1.
-    let m = _mm512_mask_set1_epi64(__m512i::ZERO, low_lanes, -1_i64);
+    let m = _mm512_mask_set1_epi64(_mm512_setzero_si512(), low_lanes, -1_i64);
 
     // Get intended MSB + 1, subtract 1 to fill intended MSB:LSB with set bits. 1_i64 << (i % 64) - 1.
-    let bits = (1_i64 << (i & 63)).unchecked_sub(1);
+    let bits = (1_i64 << (i & 63)).wrapping_sub(1);
 
     // Set dst[i:((i / 64) * 64)] = 1.
     _mm512_mask_set1_epi64(m, lane, bits)
@@ -245,12 +354,12 @@ pub unsafe fn _mm512_mask_upto_si512_custom(i: usize) -> __m512i {
 /// # Examples
 ///
 /// ```
-/// # use core::arch::x86_64::*;
 /// # use myers_ed::avx512::plumbing::_mm512_popcnt_si512_custom;
+/// # use myers_ed::avx512::u512::U512;
 /// # fn main() {
-/// let a: __m512i = unsafe { core::mem::transmute::<[i64; 8], _>([1, 0, 0, 0, 0, 0, 0, 15]) };
+/// let a = U512::from([1, 0, 0, 0, 0, 0, 0, 15]);
 ///
-/// assert_eq!(5, _mm512_popcnt_si512_custom(a));
+/// assert_eq!(5, _mm512_popcnt_si512_custom(a.into()));
 /// # }
 /// ```
 #[inline(always)]
@@ -263,6 +372,7 @@ pub fn _mm512_popcnt_si512_custom(a: __m512i) -> i64 {
         _mm512_reduce_add_epi64(_mm512_popcnt_epi64(a))
     }
 
-    // Safety: we guarantee that avx512f and avx512vpopcntdq are present when the avx512 crate feature compiles.
+    // Safety: we guarantee that avx512f and avx512vpopcntdq are present when the avx512 crate feature
+    // compiles, or when reached through runtime dispatch.
     unsafe { __inner_mm512_popcnt_si512_custom(a) }
 }
@@ -0,0 +1,34 @@
+use core::arch::x86_64::*;
+
+use crate::generic::search::{Search, myers_ed_search_with_peq};
+use crate::peq::Peq;
+
+/// Perform Myers algorithm in search mode to find every end position in `text` of a substring
+/// within edit distance `k` of the string encoded in `peq`. Uses SIMD AVX-512 with 512-bit words,
+/// so the pattern must be `<= 512` bytes. Yields `(end_pos, distance)`. See
+/// [`myers_ed_search_with_peq`].
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::avx512::search::myers_ed_search_avx512_with_peq;
+/// # use myers_ed::peq::SingleWordPeq;
+/// # fn main() {
+/// let primer = b"ACGTACGTTGCA".repeat(10);
+/// let mut read = b"TTTT".repeat(100);
+/// read.splice(200..200, primer.iter().copied());
+/// read[250] = b'C';
+///
+/// let peq = SingleWordPeq::from_bytes(&primer);
+/// let hits: Vec<_> = myers_ed_search_avx512_with_peq(&peq, &read, 1).collect();
+///
+/// assert_eq!(hits, [(320, 1)]);
+/// # }
+/// ```
+pub fn myers_ed_search_avx512_with_peq<'a, P: Peq<__m512i>>(
+    peq: &'a P,
+    text: &'a [u8],
+    k: usize,
+) -> Search<'a, __m512i, P> {
+    myers_ed_search_with_peq(peq, text, k)
+}
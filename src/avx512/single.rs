@@ -2,8 +2,14 @@ use core::arch::x86_64::*;
 
 use anyhow::{Result, anyhow};
 
+#[cfg(feature = "avx512-epi32")]
 use crate::avx512::plumbing::*;
-use crate::peq::{SingleWordPeq, Word};
+use crate::generic::align::{Alignment, myers_ed_single_align_with_peq};
+use crate::generic::single::{
+    myers_ed_single_bounded_with_peq, myers_ed_single_with_peq, myers_ed_single_with_peq_and_mode,
+};
+use crate::mode::Mode;
+use crate::peq::{Peq, SingleWordPeq};
 
 /// Perform Myers algorithm to find the edit distance between `a` and `b`. Uses SIMD AVX-512 with 512-bit words.
 /// Input bytes `a` must be `<= 512` bytes. Input bytes `b` can be any length.
@@ -29,7 +35,7 @@ pub fn myers_ed_single_avx512(a: &[u8], b: &[u8]) -> usize {
 
 pub fn try_myers_ed_single_avx512(a: &[u8], b: &[u8]) -> Result<usize> {
     if a.len() > 512 {
-        return Err(anyhow!("Input must be be <= 512 bytes"));
+        return Err(anyhow!("Input must be <= 512 bytes"));
     }
 
     // Infallible: we've verified a.len() <= 512.
@@ -38,38 +44,219 @@ pub fn try_myers_ed_single_avx512(a: &[u8], b: &[u8]) -> Result<usize> {
     Ok(myers_ed_single_avx512_with_peq(&peq, b))
 }
 
-pub fn myers_ed_single_avx512_with_peq(peq: &SingleWordPeq<__m512i>, b: &[u8]) -> usize {
+pub fn myers_ed_single_avx512_with_peq<P: Peq<__m512i>>(peq: &P, b: &[u8]) -> usize {
+    myers_ed_single_with_peq(peq, b)
+}
+
+/// Like `myers_ed_single_avx512_with_peq`, but returns `None` as soon as the edit distance is known to
+/// be greater than `k`. See [`myers_ed_single_bounded_with_peq`].
+pub fn myers_ed_single_avx512_bounded_with_peq<P: Peq<__m512i>>(
+    peq: &P,
+    b: &[u8],
+    k: usize,
+) -> Option<usize> {
+    myers_ed_single_bounded_with_peq(peq, b, k)
+}
+
+/// Perform Myers algorithm to align `a` to `b` as `mode` says, returning `(distance, end)` where
+/// `b[..end]` is the part of `b` that `a` was aligned to. Uses SIMD AVX-512 with 512-bit words.
+/// Input bytes `a` must be `<= 512` bytes. Input bytes `b` can be any length. See
+/// [`myers_ed_single_with_peq_and_mode`].
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::avx512::single::myers_ed_single_avx512_with_mode;
+/// # use myers_ed::mode::Mode;
+/// # fn main() {
+/// let a = b"ACGT".repeat(100);
+/// let mut b = a.clone();
+/// b[10] = b'T';
+/// b.extend_from_slice(b"TTTTTTTT");
+///
+/// assert_eq!(myers_ed_single_avx512_with_mode(&a, &b, Mode::Prefix), (1, 400));
+/// # }
+/// ```
+pub fn myers_ed_single_avx512_with_mode(a: &[u8], b: &[u8], mode: Mode) -> (usize, usize) {
+    assert!(a.len() <= 512, "Input must be <= 512 bytes");
+
+    let peq = SingleWordPeq::from_bytes(a);
+
+    myers_ed_single_avx512_with_peq_and_mode(&peq, b, mode)
+}
+
+pub fn try_myers_ed_single_avx512_with_mode(
+    a: &[u8],
+    b: &[u8],
+    mode: Mode,
+) -> Result<(usize, usize)> {
+    if a.len() > 512 {
+        return Err(anyhow!("Input must be <= 512 bytes"));
+    }
+
+    let peq = SingleWordPeq::from_bytes(a);
+
+    Ok(myers_ed_single_avx512_with_peq_and_mode(&peq, b, mode))
+}
+
+pub fn myers_ed_single_avx512_with_peq_and_mode<P: Peq<__m512i>>(
+    peq: &P,
+    b: &[u8],
+    mode: Mode,
+) -> (usize, usize) {
+    myers_ed_single_with_peq_and_mode(peq, b, mode)
+}
+
+/// Perform Myers algorithm to find an optimal alignment of `a` to `b` as `mode` says, with its
+/// CIGAR string. Uses SIMD AVX-512 with 512-bit words. Input bytes `a` must be `<= 512` bytes. Input bytes `b` can
+/// be any length. See [`myers_ed_single_align_with_peq`].
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::avx512::single::myers_ed_single_avx512_align;
+/// # use myers_ed::mode::Mode;
+/// # fn main() {
+/// let a = b"ACGT".repeat(100);
+/// let mut b = a.clone();
+/// b[10] = b'T';
+/// b.remove(200);
+///
+/// let aln = myers_ed_single_avx512_align(&a, &b, Mode::Global);
+///
+/// assert_eq!(aln.distance, 2);
+/// assert_eq!(aln.cigar, "10=1X189=1I199=");
+/// # }
+/// ```
+pub fn myers_ed_single_avx512_align(a: &[u8], b: &[u8], mode: Mode) -> Alignment {
+    assert!(a.len() <= 512, "Input must be <= 512 bytes");
+
+    let peq = SingleWordPeq::from_bytes(a);
+
+    myers_ed_single_avx512_align_with_peq(&peq, b, mode)
+}
+
+pub fn try_myers_ed_single_avx512_align(a: &[u8], b: &[u8], mode: Mode) -> Result<Alignment> {
+    if a.len() > 512 {
+        return Err(anyhow!("Input must be <= 512 bytes"));
+    }
+
+    let peq = SingleWordPeq::from_bytes(a);
+
+    Ok(myers_ed_single_avx512_align_with_peq(&peq, b, mode))
+}
+
+pub fn myers_ed_single_avx512_align_with_peq<P: Peq<__m512i>>(
+    peq: &P,
+    b: &[u8],
+    mode: Mode,
+) -> Alignment {
+    myers_ed_single_align_with_peq(peq, b, mode)
+}
+
+/// Perform Myers algorithm to find the edit distance between `a` and `b`, adding 512-bit words as
+/// sixteen 32-bit lanes. Uses SIMD AVX-512. Input bytes `a` must be `<= 512` bytes. Input bytes
+/// `b` can be any length.
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::avx512::single::myers_ed_single_avx512_epi32;
+/// # fn main() {
+/// let a = [b'A'; 500];
+/// let mut b = [b'A'; 500];
+/// b[100] = b'C';
+///
+/// assert_eq!(myers_ed_single_avx512_epi32(&a, &b), 1);
+/// # }
+/// ```
+#[cfg(feature = "avx512-epi32")]
+pub fn myers_ed_single_avx512_epi32(a: &[u8], b: &[u8]) -> usize {
+    assert!(a.len() <= 512, "Input must be <= 512 bytes");
+
+    // Infallible: we've verified a.len() <= 512.
+    let peq = SingleWordPeq::from_bytes(a);
+
+    myers_ed_single_avx512_epi32_with_peq(&peq, b)
+}
+
+#[cfg(feature = "avx512-epi32")]
+pub fn try_myers_ed_single_avx512_epi32(a: &[u8], b: &[u8]) -> Result<usize> {
+    if a.len() > 512 {
+        return Err(anyhow!("Input must be <= 512 bytes"));
+    }
+
+    // Infallible: we've verified a.len() <= 512.
+    let peq = SingleWordPeq::from_bytes(a);
+
+    Ok(myers_ed_single_avx512_epi32_with_peq(&peq, b))
+}
+
+/// 32-bit lane alternative to [`myers_ed_single_avx512_with_peq`]. The 512-bit add is done as
+/// sixteen independent 32-bit lane adds, and the carries between lanes are collected in a mask and
+/// added back afterwards. We predict that no carry ripples through a lane summing to all ones,
+/// which only needs a mask shift, and fall back to full carry-lookahead when that fails. The
+/// one-bit shifts across lanes are funnel shifts of each lane with its lower neighbour.
+///
+/// Every carry is resolved within the column that produced it. This is not the deferred
+/// carry-save loop sketched in `overhaul_intel.c`, which predicts the next column's carries off
+/// the critical path and is still unfinished.
+#[cfg(feature = "avx512-epi32")]
+pub fn myers_ed_single_avx512_epi32_with_peq<P: Peq<__m512i>>(peq: &P, b: &[u8]) -> usize {
     // Safety
     //
-    // The `avx512f` and `avx512vpopcntdq `target_features` must be available.
+    // The `avx512f`, `avx512bw`, `avx512vbmi2` and `avx512vpopcntdq` `target_features` must be
+    // available.
     #[inline(always)]
-    unsafe fn __inner_myers_ed_single_avx512_with_peq(
-        peq: &SingleWordPeq<__m512i>,
+    unsafe fn __inner_myers_ed_single_avx512_epi32_with_peq<P: Peq<__m512i>>(
+        peq: &P,
         b: &[u8],
     ) -> usize {
+        let zero = _mm512_setzero_si512();
+        let ones = _mm512_set1_epi32(-1);
+
+        // A 1 in every 32-bit lane, for adding the saved carries into the lanes receiving them.
+        let lane_one = _mm512_set1_epi32(1);
+
+        // Only the MSB of lane 15 set. Funnelled into lane 0, this shifts a 1 into the LSB.
+        let one_msb = _mm512_mask_set1_epi32(zero, 1 << 15, i32::MIN);
+
         // Vertical positive delta bit-vector.
-        let mut vp = _mm512_set1_epi64(-1_i64);
+        let mut vp = ones;
 
         // Vertical negative delta bit-vector.
-        let mut vn = _mm512_setzero_si512();
+        let mut vn = zero;
 
         // Update loop.
         for &x in b {
             // Get the equality mask for the current character.
-            //
-            // Infallible: `x as usize` \in [0, 255] and `peq.peq.len() == 256`.
-            let eq = peq[x as usize];
-
-            // Calculate diagonal zero delta bit-vector. This is d0 = (((eq & vp) + vp) ^ vp) | eq.
-            let d0 = _mm512_ternarylogic_epi64(
-                _mm512_add_si512_custom::<1>(_mm512_and_si512(eq, vp), vp),
-                vp,
-                eq,
-                0xBE,
-            );
+            let eq = peq.get(x);
+
+            // Add (eq & vp) + vp as sixteen independent 32-bit lanes.
+            let s = _mm512_add_epi32(_mm512_and_si512(eq, vp), vp);
+
+            // Mask of lanes generating a carry. If s < vp, then we overflowed.
+            let g: __mmask16 = _mm512_cmplt_epu32_mask(s, vp);
+
+            // Mask of lanes propagating a carry. If s = 0xFFFFFFFF, an incoming carry overflows
+            // this lane too.
+            let p: __mmask16 = _mm512_cmpeq_epi32_mask(s, ones);
+
+            // Predict that each carry only moves up one lane. The prediction only fails when a
+            // carry lands on a propagating lane, in which case we resolve the chains properly.
+            let mut c = g << 1;
+            if core::hint::unlikely(c & p != 0) {
+                c = c.wrapping_add(p) ^ p;
+            }
+
+            // Add the saved carries into their lanes. The carry out of lane 15 is discarded.
+            let sum = _mm512_mask_add_epi32(s, c, s, lane_one);
+
+            // Calculate diagonal zero delta bit-vector. This is d0 = (sum ^ vp) | eq.
+            let d0 = _mm512_ternarylogic_epi32(sum, vp, eq, 0xBE);
 
             // Calculate horizontal positive delta bit-vector. This is hp = vn | !(vp | d0).
-            let mut hp = _mm512_ternarylogic_epi64(vn, vp, d0, 0xF1);
+            let hp = _mm512_ternarylogic_epi32(vn, vp, d0, 0xF1);
 
             // Calculate horizontal negative delta bit-vector.
             let hn = _mm512_and_si512(vp, d0);
@@ -77,22 +264,22 @@ pub fn myers_ed_single_avx512_with_peq(peq: &SingleWordPeq<__m512i>, b: &[u8]) -
             // Calculate intermediate mask for next column's vertical delta bits.
             let xh = _mm512_or_si512(eq, vn);
 
-            // Move one column right in DP matrix. Uses custom `Word` trait for `ONE` const.
-            // This is hp = (hp << 1_u32) | 1_m512i.
-            hp = _mm512_or_si512(_mm512_slli_si512_custom::<1>(hp), __m512i::ONE);
+            // Move one column right in DP matrix. Each lane is funnel shifted with the lane below
+            // it, so this is hp = (hp << 1) | 1 and hn = hn << 1 across the full 512 bits.
+            let hp = _mm512_shrdi_epi32::<31>(_mm512_alignr_epi32::<15>(hp, one_msb), hp);
+            let hn = _mm512_shrdi_epi32::<31>(_mm512_alignr_epi32::<15>(hn, zero), hn);
 
-            // Update positive vertical delta bit-vector. This is (hn << 1_u32) | !(xh | hp).
-            vp = _mm512_ternarylogic_epi64(_mm512_slli_si512_custom::<1>(hn), xh, hp, 0xF1);
+            // Update positive vertical delta bit-vector. This is hn | !(xh | hp).
+            vp = _mm512_ternarylogic_epi32(hn, xh, hp, 0xF1);
 
             // Update negative vertical delta bit-vector.
             vn = _mm512_and_si512(hp, xh);
         }
 
-        // Compute mask to get only real bits. This is dst[l:0] = 1 and dst[511:l+1] = 0,
-        // for `l = peq.len()`.
+        // Compute mask to get only real bits.
         //
-        // Safety: `peq.len()` must be `<=512`, which is guaranteed by `SingleWordPeq`.
-        let m = unsafe { _mm512_mask_upto_si512_custom(peq.len()) };
+        // Safety: `Peq` guarantees that `peq.len() <= 512`.
+        let m = _mm512_mask_upto_si512_custom(peq.len());
 
         // Compute final edit distance.
         let vp_popcnt = _mm512_popcnt_si512_custom(_mm512_and_si512(vp, m)) as usize;
@@ -101,6 +288,7 @@ pub fn myers_ed_single_avx512_with_peq(peq: &SingleWordPeq<__m512i>, b: &[u8]) -
         b.len() + vp_popcnt - vn_popcnt
     }
 
-    // Safety: we guarantee that avx512f and avx512vpopcntdq are present when the avx512 crate feature compiles.
-    unsafe { __inner_myers_ed_single_avx512_with_peq(peq, b) }
+    // Safety: we guarantee that avx512f, avx512bw, avx512vbmi2 and avx512vpopcntdq are present when
+    // the avx512-epi32 crate feature compiles.
+    unsafe { __inner_myers_ed_single_avx512_epi32_with_peq(peq, b) }
 }
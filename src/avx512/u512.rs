@@ -0,0 +1,234 @@
+//! A 512-bit unsigned integer built on the AVX-512 helpers in [`plumbing`](crate::avx512::plumbing).
+
+use core::fmt;
+use core::ops::{Add, BitAnd, BitOr, BitXor, Not, Shl, Shr};
+
+use crate::avx512::arch::*;
+use crate::avx512::plumbing::*;
+
+// Lane indices `0..8`, for building lane permutes.
+//
+// Safety: `__m512i` has an exact underlying representation of `[u64; 8]`.
+const IOTA: __m512i =
+    unsafe { core::mem::transmute::<[u64; 8], __m512i>([0, 1, 2, 3, 4, 5, 6, 7]) };
+
+// Every unsafe block below relies on avx512f being present, which the avx512 crate feature asserts
+// at compile time. Under the avx512-emulated crate feature, the intrinsics need no target features.
+
+/// A 512-bit unsigned integer held in one `__m512i`. Lane 0 holds the least significant 64 bits.
+/// Arithmetic wraps modulo 2^512.
+///
+/// Convert from and to `[u64; 8]` to inspect values, and from and to `__m512i` to pass values to
+/// and from kernels and the `plumbing` functions.
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::avx512::u512::U512;
+/// # fn main() {
+/// // The carry out of lane 0 ripples into lane 1.
+/// let a = U512::from([u64::MAX, 0, 0, 0, 0, 0, 0, 0]);
+///
+/// assert_eq!(a + U512::ONE, U512::ONE << 64);
+/// assert_eq!((a << 100) >> 100, a);
+/// assert_eq!(<[u64; 8]>::from(!a), [0, u64::MAX, u64::MAX, u64::MAX, u64::MAX, u64::MAX, u64::MAX, u64::MAX]);
+/// assert_eq!((U512::MAX ^ a).count_ones(), 448);
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+pub struct U512(__m512i);
+
+impl U512 {
+    /// The value `0`.
+    // Safety: `__m512i` has an exact underlying representation of `[u64; 8]`.
+    pub const ZERO: U512 = U512(unsafe { core::mem::transmute::<[u64; 8], __m512i>([0; 8]) });
+
+    /// The value `1`.
+    // Safety: `__m512i` has an exact underlying representation of `[u64; 8]`.
+    pub const ONE: U512 =
+        U512(unsafe { core::mem::transmute::<[u64; 8], __m512i>([1, 0, 0, 0, 0, 0, 0, 0]) });
+
+    /// The value `2^512 - 1`, with all bits set.
+    // Safety: `__m512i` has an exact underlying representation of `[u64; 8]`.
+    pub const MAX: U512 = U512(unsafe { core::mem::transmute::<[u64; 8], __m512i>([u64::MAX; 8]) });
+
+    /// Return the number of set bits.
+    #[inline(always)]
+    pub fn count_ones(self) -> u32 {
+        _mm512_popcnt_si512_custom(self.0) as u32
+    }
+}
+
+impl Default for U512 {
+    #[inline(always)]
+    fn default() -> U512 {
+        U512::ZERO
+    }
+}
+
+impl From<[u64; 8]> for U512 {
+    #[inline(always)]
+    fn from(a: [u64; 8]) -> U512 {
+        // Safety: `__m512i` has an exact underlying representation of `[u64; 8]`.
+        U512(unsafe { core::mem::transmute::<[u64; 8], __m512i>(a) })
+    }
+}
+
+impl From<U512> for [u64; 8] {
+    #[inline(always)]
+    fn from(a: U512) -> [u64; 8] {
+        // Safety: `__m512i` has an exact underlying representation of `[u64; 8]`.
+        unsafe { core::mem::transmute::<__m512i, [u64; 8]>(a.0) }
+    }
+}
+
+impl From<__m512i> for U512 {
+    #[inline(always)]
+    fn from(a: __m512i) -> U512 {
+        U512(a)
+    }
+}
+
+impl From<U512> for __m512i {
+    #[inline(always)]
+    fn from(a: U512) -> __m512i {
+        a.0
+    }
+}
+
+impl PartialEq for U512 {
+    #[inline(always)]
+    fn eq(&self, other: &U512) -> bool {
+        // Safety: avx512f is present, see above.
+        unsafe { _mm512_cmpeq_epi64_mask(self.0, other.0) == 0xFF }
+    }
+}
+
+impl Eq for U512 {}
+
+impl fmt::Debug for U512 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("U512")
+            .field(&<[u64; 8]>::from(*self))
+            .finish()
+    }
+}
+
+impl Add for U512 {
+    type Output = U512;
+
+    #[inline(always)]
+    fn add(self, rhs: U512) -> U512 {
+        U512(_mm512_add_si512_lookahead_custom(self.0, rhs.0))
+    }
+}
+
+impl BitAnd for U512 {
+    type Output = U512;
+
+    #[inline(always)]
+    fn bitand(self, rhs: U512) -> U512 {
+        // Safety: avx512f is present, see above.
+        U512(unsafe { _mm512_and_si512(self.0, rhs.0) })
+    }
+}
+
+impl BitOr for U512 {
+    type Output = U512;
+
+    #[inline(always)]
+    fn bitor(self, rhs: U512) -> U512 {
+        // Safety: avx512f is present, see above.
+        U512(unsafe { _mm512_or_si512(self.0, rhs.0) })
+    }
+}
+
+impl BitXor for U512 {
+    type Output = U512;
+
+    #[inline(always)]
+    fn bitxor(self, rhs: U512) -> U512 {
+        // Safety: avx512f is present, see above.
+        U512(unsafe { _mm512_xor_si512(self.0, rhs.0) })
+    }
+}
+
+impl Not for U512 {
+    type Output = U512;
+
+    #[inline(always)]
+    fn not(self) -> U512 {
+        // Safety: avx512f is present, see above.
+        U512(unsafe { _mm512_ternarylogic_epi64::<0x55>(self.0, self.0, self.0) })
+    }
+}
+
+impl Shl<u32> for U512 {
+    type Output = U512;
+
+    /// Shift left by `n` bits, shifting in zeros. Shifting by 512 or more gives zero.
+    #[inline(always)]
+    fn shl(self, n: u32) -> U512 {
+        // Safety
+        //
+        // `n` must be less than 512. The `avx512f` `target_feature` must be available.
+        #[inline(always)]
+        unsafe fn __inner_shl(a: __m512i, n: u32) -> __m512i {
+            // Whole lanes and remaining bits to shift by.
+            let (q, r) = (n >> 6, (n & 63) as i64);
+
+            // Move lane i - q into lane i, and zero the bottom q lanes.
+            let idx = _mm512_add_epi64(IOTA, _mm512_set1_epi64(-(q as i64)));
+            let a = _mm512_maskz_permutexvar_epi64(0xFF << q, idx, a);
+
+            // Left shift lanes by r, and fill the shifted in zeros with the bits that overflowed
+            // the previous lane. A shift by 64 gives zero, so r = 0 needs no special case.
+            let s = _mm512_sllv_epi64(a, _mm512_set1_epi64(r));
+            let o = _mm512_srlv_epi64(a, _mm512_set1_epi64(64 - r));
+
+            _mm512_or_si512(s, _mm512_alignr_epi64::<7>(o, _mm512_setzero_si512()))
+        }
+
+        if n >= 512 {
+            return U512::ZERO;
+        }
+
+        // Safety: `n < 512`, and avx512f is present, see above.
+        U512(unsafe { __inner_shl(self.0, n) })
+    }
+}
+
+impl Shr<u32> for U512 {
+    type Output = U512;
+
+    /// Shift right by `n` bits, shifting in zeros. Shifting by 512 or more gives zero.
+    #[inline(always)]
+    fn shr(self, n: u32) -> U512 {
+        // Safety
+        //
+        // `n` must be less than 512. The `avx512f` `target_feature` must be available.
+        #[inline(always)]
+        unsafe fn __inner_shr(a: __m512i, n: u32) -> __m512i {
+            // Whole lanes and remaining bits to shift by.
+            let (q, r) = (n >> 6, (n & 63) as i64);
+
+            // Move lane i + q into lane i, and zero the top q lanes.
+            let idx = _mm512_add_epi64(IOTA, _mm512_set1_epi64(q as i64));
+            let a = _mm512_maskz_permutexvar_epi64(0xFF >> q, idx, a);
+
+            // Right shift lanes by r, and fill the shifted in zeros with the bits that underflowed
+            // the next lane. A shift by 64 gives zero, so r = 0 needs no special case.
+            let s = _mm512_srlv_epi64(a, _mm512_set1_epi64(r));
+            let o = _mm512_sllv_epi64(a, _mm512_set1_epi64(64 - r));
+
+            _mm512_or_si512(s, _mm512_alignr_epi64::<1>(_mm512_setzero_si512(), o))
+        }
+
+        if n >= 512 {
+            return U512::ZERO;
+        }
+
+        // Safety: `n < 512`, and avx512f is present, see above.
+        U512(unsafe { __inner_shr(self.0, n) })
+    }
+}
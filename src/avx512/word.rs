@@ -0,0 +1,175 @@
+//! [`Word`] implementation for 512-bit AVX-512 vectors.
+
+use crate::avx512::arch::*;
+
+use crate::avx512::plumbing::*;
+use crate::word::{Unchecked, Word};
+
+// Every unsafe block below relies on avx512f, avx512bw and avx512vpopcntdq being present. This
+// holds either because the avx512 crate feature asserts them at compile time, or because
+// `Unchecked<__m512i>` is only ever used behind runtime detection in `crate::dispatch`. Under the
+// avx512-emulated crate feature, the intrinsics are portable and need no target features.
+impl Word for Unchecked<__m512i> {
+    // Safety: `__m512i` is POD so this value is valid.
+    const ZERO: Self = Unchecked(unsafe { core::mem::zeroed() });
+
+    // Safety: `__m512i` has an exact underlying representation of `[i64; 8]`.
+    const ONE: Self =
+        Unchecked(unsafe { core::mem::transmute::<[i64; 8], __m512i>([1, 0, 0, 0, 0, 0, 0, 0]) });
+
+    // Safety: `__m512i` has an exact underlying representation of `[i64; 8]`.
+    const ONES: Self = Unchecked(unsafe { core::mem::transmute::<[i64; 8], __m512i>([-1; 8]) });
+
+    #[inline(always)]
+    unsafe fn bit_at_unchecked(i: usize) -> Self {
+        // Mask for lane containing bit to set.
+        let k = 1_u8 << (i >> 6);
+
+        // Set bit inside selected lane.
+        let a = 1_i64 << (i & 63);
+
+        Unchecked(_mm512_mask_set1_epi64(Self::ZERO.0, k, a))
+    }
+
+    #[inline(always)]
+    unsafe fn bit_test_unchecked(self, i: usize) -> bool {
+        _mm512_test_epi64_mask(self.0, Self::bit_at_unchecked(i).0) != 0
+    }
+
+    #[inline(always)]
+    fn bit_or(self, rhs: Self) -> Self {
+        // Safety: avx512f is present, see above.
+        Unchecked(unsafe { _mm512_or_si512(self.0, rhs.0) })
+    }
+
+    #[inline(always)]
+    fn bit_and(self, rhs: Self) -> Self {
+        // Safety: avx512f is present, see above.
+        Unchecked(unsafe { _mm512_and_si512(self.0, rhs.0) })
+    }
+
+    #[inline(always)]
+    fn bit_xor(self, rhs: Self) -> Self {
+        // Safety: avx512f is present, see above.
+        Unchecked(unsafe { _mm512_xor_si512(self.0, rhs.0) })
+    }
+
+    #[inline(always)]
+    fn bit_not(self) -> Self {
+        // Safety: avx512f is present, see above.
+        Unchecked(unsafe { _mm512_ternarylogic_epi64::<0x55>(self.0, self.0, self.0) })
+    }
+
+    #[inline(always)]
+    fn wide_add(self, rhs: Self) -> Self {
+        Unchecked(_mm512_add_si512_lookahead_custom(self.0, rhs.0))
+    }
+
+    #[inline(always)]
+    fn shl_one(self, carry: bool) -> Self {
+        let s = _mm512_slli_si512_custom::<1>(self.0);
+
+        // Safety: avx512f is present, see above.
+        Unchecked(unsafe { _mm512_mask_or_epi64(s, carry as __mmask8, s, Self::ONE.0) })
+    }
+
+    #[inline(always)]
+    fn shr_one(self) -> Self {
+        Unchecked(_mm512_srli_si512_custom::<1>(self.0))
+    }
+
+    #[inline(always)]
+    unsafe fn shr_unchecked(self, n: usize) -> Self {
+        // Safety: `__m512i` has an exact underlying representation of `[i64; 8]`.
+        const LANES: __m512i =
+            unsafe { core::mem::transmute::<[i64; 8], __m512i>([0, 1, 2, 3, 4, 5, 6, 7]) };
+
+        // Caller guarantees that `n < 512`, so `q < 8`.
+        let (q, r) = (n >> 6, (n & 63) as i64);
+
+        // Move lane i + q, and the lane above it, down into lane i, zeroing lanes past the top.
+        let idx = _mm512_add_epi64(LANES, _mm512_set1_epi64(q as i64));
+        let lo = _mm512_maskz_permutexvar_epi64((0xFF_u32 >> q) as u8, idx, self.0);
+        let hi = _mm512_maskz_permutexvar_epi64(
+            (0x7F_u32 >> q) as u8,
+            _mm512_add_epi64(idx, _mm512_set1_epi64(1)),
+            self.0,
+        );
+
+        // Variable shifts by 64 or more give zero, so a zero `r` moves nothing from `hi`.
+        let lo = _mm512_srlv_epi64(lo, _mm512_set1_epi64(r));
+        let hi = _mm512_sllv_epi64(hi, _mm512_set1_epi64(64 - r));
+
+        Unchecked(_mm512_or_si512(lo, hi))
+    }
+
+    #[inline(always)]
+    fn msb(self) -> bool {
+        // The sign bit of lane 7 is bit 511, so a signed compare against zero extracts it.
+        //
+        // Safety: avx512f is present, see above.
+        unsafe { _mm512_cmplt_epi64_mask(self.0, Self::ZERO.0) >> 7 == 1 }
+    }
+
+    #[inline(always)]
+    unsafe fn popcnt_upto_unchecked(self, n: usize) -> usize {
+        // Safety: caller guarantees that `n <= 512`.
+        let m = _mm512_mask_upto_si512_custom(n);
+
+        _mm512_popcnt_si512_custom(_mm512_and_si512(self.0, m)) as usize
+    }
+
+    /// Eight 64x8-bit lanes and a mask of the real bytes in each lane.
+    type Spread = ([__m512i; 8], [__mmask64; 8]);
+
+    #[inline(always)]
+    unsafe fn spread_bytes_unchecked(s: &[u8]) -> ([__m512i; 8], [__mmask64; 8]) {
+        let mut lanes = [Self::ZERO.0; 8];
+        let mut masks = [0; 8];
+
+        for (i, chunk) in s.chunks(64).enumerate() {
+            // Mask of the real bytes in this chunk. A full chunk would overflow the shift.
+            masks[i] = 1_u64
+                .checked_shl(chunk.len() as u32)
+                .unwrap_or(0)
+                .wrapping_sub(1);
+
+            // Masked loads suppress faults on masked-out bytes, so we never read past `s`.
+            lanes[i] = _mm512_maskz_loadu_epi8(masks[i], chunk.as_ptr() as *const i8);
+        }
+
+        (lanes, masks)
+    }
+
+    #[inline(always)]
+    fn eq_mask(spread: &([__m512i; 8], [__mmask64; 8]), c: u8) -> Self {
+        // Safety
+        //
+        // The `avx512f` and `avx512bw` `target_features` must be available.
+        #[inline(always)]
+        unsafe fn __inner_eq_mask(spread: &([__m512i; 8], [__mmask64; 8]), c: u8) -> __m512i {
+            let (lanes, masks) = spread;
+
+            // Broadcast symbol across all 64 lanes.
+            let c = _mm512_set1_epi8(c as i8);
+
+            // Each 64-byte lane compares into exactly one 64-bit lane of the result. Inserting
+            // each lane from a register avoids a store-forwarding stall on reading them back.
+            let mut eq = _mm512_setzero_si512();
+            for (i, (&l, &m)) in lanes.iter().zip(masks.iter()).enumerate() {
+                let lane = _mm512_mask_cmpeq_epi8_mask(m, l, c);
+                eq = _mm512_mask_set1_epi64(eq, 1 << i, lane as i64);
+            }
+
+            eq
+        }
+
+        // Safety: avx512f and avx512bw are present, see above.
+        Unchecked(unsafe { __inner_eq_mask(spread, c) })
+    }
+}
+
+// Safety: the avx512 crate feature asserts the required target features at compile time, and the
+// avx512-emulated crate feature needs none.
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
+crate::macros::forward_word!(__m512i, ([__m512i; 8], [__mmask64; 8]));
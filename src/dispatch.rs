@@ -0,0 +1,226 @@
+//! Runtime CPU dispatch. Target features are detected once per process, and every SIMD kernel here
+//! is compiled with `#[target_feature]` instead of relying on crate-wide `-C target-feature`, so a
+//! single binary runs the widest backend available on whichever x86-64 CPU it lands on.
+
+use core::arch::x86_64::*;
+use std::sync::OnceLock;
+
+use anyhow::Result;
+
+use crate::generic::multi::myers_ed_multi_with_peq;
+use crate::generic::single::myers_ed_single_with_peq;
+use crate::peq::{MultiWordPeq, SingleWordPeq};
+use crate::word::Unchecked;
+
+/// Backends that [`myers_ed`] can dispatch to, ordered from narrowest to widest.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Backend {
+    /// 64-bit words, or chains of them. Always available.
+    Scalar,
+    /// 256-bit AVX-2 words. Requires `avx2`.
+    Avx2,
+    /// 512-bit AVX-512 words. Requires `avx512f`, `avx512bw` and `avx512vpopcntdq`, and is never
+    /// picked when the `avx512-emulated` crate feature is enabled without `avx512`.
+    Avx512,
+}
+
+impl Backend {
+    /// Detect the widest backend supported by this CPU. CPUID is only queried on the first call,
+    /// after which the result is cached for the lifetime of the process.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use myers_ed::dispatch::Backend;
+    /// # fn main() {
+    /// let backend = Backend::detect();
+    ///
+    /// assert!(backend >= Backend::Scalar);
+    /// assert_eq!(backend, Backend::detect());
+    /// # }
+    /// ```
+    pub fn detect() -> Backend {
+        static BACKEND: OnceLock<Backend> = OnceLock::new();
+
+        // The avx512-emulated crate feature swaps the AVX-512 intrinsics for a portable emulation,
+        // which is far slower than even the scalar backend, so it is never worth dispatching to.
+        let avx512 = !cfg!(all(feature = "avx512-emulated", not(feature = "avx512")));
+
+        *BACKEND.get_or_init(|| {
+            if avx512
+                && is_x86_feature_detected!("avx512f")
+                && is_x86_feature_detected!("avx512bw")
+                && is_x86_feature_detected!("avx512vpopcntdq")
+            {
+                Backend::Avx512
+            } else if is_x86_feature_detected!("avx2") {
+                Backend::Avx2
+            } else {
+                Backend::Scalar
+            }
+        })
+    }
+
+    /// Number of bits in a single word of this backend.
+    pub const fn bits(self) -> usize {
+        match self {
+            Backend::Scalar => 64,
+            Backend::Avx2 => 256,
+            Backend::Avx512 => 512,
+        }
+    }
+}
+
+/// Peq built for whichever backend [`Backend::detect`] picks, laid out as single or multiple words
+/// depending on the pattern length. Use with [`myers_ed_with_peq`].
+pub struct DispatchPeq {
+    peq: DispatchPeqInner,
+    len: usize,
+}
+
+/// Invariant: SIMD variants are only constructed once their target features have been detected.
+enum DispatchPeqInner {
+    Scalar(Box<SingleWordPeq<u64>>),
+    ScalarMulti(MultiWordPeq<u64>),
+    Avx2(Box<SingleWordPeq<Unchecked<__m256i>>>),
+    Avx2Multi(MultiWordPeq<Unchecked<__m256i>>),
+    Avx512(Box<SingleWordPeq<Unchecked<__m512i>>>),
+    Avx512Multi(MultiWordPeq<Unchecked<__m512i>>),
+}
+
+impl DispatchPeq {
+    pub fn from_bytes<B: AsRef<[u8]>>(s: B) -> DispatchPeq {
+        // Infallible except on allocation failure, which aborts like any other `Vec` would.
+        Self::try_from_bytes(s).expect("Failed to allocate Peq")
+    }
+
+    pub fn try_from_bytes<B: AsRef<[u8]>>(s: B) -> Result<DispatchPeq> {
+        // Safety
+        //
+        // The `avx2` `target_feature` must be available.
+        #[target_feature(enable = "avx2")]
+        unsafe fn __inner_avx2_from_bytes(s: &[u8]) -> Result<DispatchPeqInner> {
+            Ok(if s.len() <= 256 {
+                DispatchPeqInner::Avx2(Box::new(SingleWordPeq::from_bytes(s)))
+            } else {
+                DispatchPeqInner::Avx2Multi(MultiWordPeq::try_from_bytes(s)?)
+            })
+        }
+
+        // Safety
+        //
+        // The `avx512f`, `avx512bw` and `avx512vpopcntdq` `target_features` must be available.
+        #[target_feature(enable = "avx512f,avx512bw,avx512vpopcntdq")]
+        unsafe fn __inner_avx512_from_bytes(s: &[u8]) -> Result<DispatchPeqInner> {
+            Ok(if s.len() <= 512 {
+                DispatchPeqInner::Avx512(Box::new(SingleWordPeq::from_bytes(s)))
+            } else {
+                DispatchPeqInner::Avx512Multi(MultiWordPeq::try_from_bytes(s)?)
+            })
+        }
+
+        let s = s.as_ref();
+
+        // A single 64-bit word is always fastest when the whole pattern fits in it.
+        let peq = match Backend::detect() {
+            _ if s.len() <= 64 => DispatchPeqInner::Scalar(Box::new(SingleWordPeq::from_bytes(s))),
+            Backend::Scalar => DispatchPeqInner::ScalarMulti(MultiWordPeq::try_from_bytes(s)?),
+            // Safety: `Backend::detect` found avx2.
+            Backend::Avx2 => unsafe { __inner_avx2_from_bytes(s)? },
+            // Safety: `Backend::detect` found avx512f, avx512bw and avx512vpopcntdq.
+            Backend::Avx512 => unsafe { __inner_avx512_from_bytes(s)? },
+        };
+
+        Ok(DispatchPeq { peq, len: s.len() })
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Backend that this Peq was built for, and that [`myers_ed_with_peq`] will run on.
+    pub fn backend(&self) -> Backend {
+        match self.peq {
+            DispatchPeqInner::Scalar(_) | DispatchPeqInner::ScalarMulti(_) => Backend::Scalar,
+            DispatchPeqInner::Avx2(_) | DispatchPeqInner::Avx2Multi(_) => Backend::Avx2,
+            DispatchPeqInner::Avx512(_) | DispatchPeqInner::Avx512Multi(_) => Backend::Avx512,
+        }
+    }
+}
+
+/// Perform Myers algorithm to find the edit distance between `a` and `b`, on the widest backend
+/// this CPU supports for the length of `a`. Input bytes `a` and `b` can both be any length.
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::dispatch::myers_ed;
+/// # fn main() {
+/// assert_eq!(myers_ed(b"ACCCT", b"ACCTT"), 1);
+///
+/// let a = [b'A'; 1000];
+/// let mut b = [b'A'; 1000];
+/// b[600] = b'C';
+///
+/// assert_eq!(myers_ed(&a, &b), 1);
+/// # }
+/// ```
+pub fn myers_ed(a: &[u8], b: &[u8]) -> usize {
+    let peq = DispatchPeq::from_bytes(a);
+
+    myers_ed_with_peq(&peq, b)
+}
+
+pub fn try_myers_ed(a: &[u8], b: &[u8]) -> Result<usize> {
+    let peq = DispatchPeq::try_from_bytes(a)?;
+
+    Ok(myers_ed_with_peq(&peq, b))
+}
+
+pub fn myers_ed_with_peq(peq: &DispatchPeq, b: &[u8]) -> usize {
+    // Safety
+    //
+    // The `avx2` `target_feature` must be available.
+    #[target_feature(enable = "avx2")]
+    unsafe fn __inner_avx2_single(peq: &SingleWordPeq<Unchecked<__m256i>>, b: &[u8]) -> usize {
+        myers_ed_single_with_peq(peq, b)
+    }
+
+    // Safety
+    //
+    // The `avx2` `target_feature` must be available.
+    #[target_feature(enable = "avx2")]
+    unsafe fn __inner_avx2_multi(peq: &MultiWordPeq<Unchecked<__m256i>>, b: &[u8]) -> usize {
+        myers_ed_multi_with_peq(peq, b)
+    }
+
+    // Safety
+    //
+    // The `avx512f`, `avx512bw` and `avx512vpopcntdq` `target_features` must be available.
+    #[target_feature(enable = "avx512f,avx512bw,avx512vpopcntdq")]
+    unsafe fn __inner_avx512_single(peq: &SingleWordPeq<Unchecked<__m512i>>, b: &[u8]) -> usize {
+        myers_ed_single_with_peq(peq, b)
+    }
+
+    // Safety
+    //
+    // The `avx512f`, `avx512bw` and `avx512vpopcntdq` `target_features` must be available.
+    #[target_feature(enable = "avx512f,avx512bw,avx512vpopcntdq")]
+    unsafe fn __inner_avx512_multi(peq: &MultiWordPeq<Unchecked<__m512i>>, b: &[u8]) -> usize {
+        myers_ed_multi_with_peq(peq, b)
+    }
+
+    // Safety: `DispatchPeqInner` only holds SIMD variants once their target features are detected.
+    match &peq.peq {
+        DispatchPeqInner::Scalar(peq) => myers_ed_single_with_peq(peq.as_ref(), b),
+        DispatchPeqInner::ScalarMulti(peq) => myers_ed_multi_with_peq(peq, b),
+        DispatchPeqInner::Avx2(peq) => unsafe { __inner_avx2_single(peq, b) },
+        DispatchPeqInner::Avx2Multi(peq) => unsafe { __inner_avx2_multi(peq, b) },
+        DispatchPeqInner::Avx512(peq) => unsafe { __inner_avx512_single(peq, b) },
+        DispatchPeqInner::Avx512Multi(peq) => unsafe { __inner_avx512_multi(peq, b) },
+    }
+}
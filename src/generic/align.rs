@@ -0,0 +1,161 @@
+use core::fmt::Write;
+
+use crate::generic::single::step;
+use crate::mode::Mode;
+use crate::peq::Peq;
+use crate::word::Word;
+
+/// An optimal alignment of a pattern `a` to `b[start..end]`.
+///
+/// The CIGAR string treats `a` as the query and `b` as the reference, like SAM. `=` and `X` are a
+/// match and a mismatch, `I` is a byte of `a` missing from `b`, and `D` is a byte of `b` missing
+/// from `a`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Alignment {
+    /// Start of the aligned part of `b`.
+    pub start: usize,
+    /// End of the aligned part of `b`, exclusive.
+    pub end: usize,
+    /// Edit distance between `a` and `b[start..end]`.
+    pub distance: usize,
+    /// Run-length encoded edit operations, e.g. `3=1X2=1D`.
+    pub cigar: String,
+}
+
+/// Perform Myers algorithm like [`myers_ed_single_with_peq_and_mode`], and recover an optimal
+/// alignment of the string encoded in `peq` to `b`, using a single word of type `T`. This is the
+/// kernel behind every `myers_ed_single_*_align_with_peq`.
+///
+/// The vertical deltas and diagonal zeros of every column are kept while the DP runs, so this
+/// needs `2 * b.len()` words of memory. The traceback then walks back from `(m, end)` to row 0,
+/// reading each step off those bits, and prefers diagonal moves.
+///
+/// [`myers_ed_single_with_peq_and_mode`]: crate::generic::single::myers_ed_single_with_peq_and_mode
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::generic::align::myers_ed_single_align_with_peq;
+/// # use myers_ed::mode::Mode;
+/// # use myers_ed::peq::SingleWordPeq;
+/// # fn main() {
+/// let peq = SingleWordPeq::<u64>::from_bytes(b"ACGTACGT");
+/// let aln = myers_ed_single_align_with_peq(&peq, b"ACTTACCGT", Mode::Global);
+///
+/// assert_eq!(aln.distance, 2);
+/// assert_eq!(aln.cigar, "2=1X2=1D3=");
+/// # }
+/// ```
+#[inline(always)]
+pub fn myers_ed_single_align_with_peq<T: Word, P: Peq<T>>(
+    peq: &P,
+    b: &[u8],
+    mode: Mode,
+) -> Alignment {
+    let m = peq.len();
+
+    let mut vp = T::ONES;
+    let mut vn = T::ZERO;
+
+    // Vertical positive deltas and diagonal zeros of column j, at index j - 1.
+    let mut cols = Vec::with_capacity(b.len());
+
+    // Score of the last pattern row, and the best score so far with the column it was reached in.
+    let mut score = m;
+    let mut best = (m, 0);
+
+    // Update loop.
+    for (j, &x) in (1..).zip(b) {
+        let eq = peq.get(x);
+
+        // Calculate diagonal zero bit-vector. This is d0 = (((eq & vp) + vp) ^ vp) | eq | vn, so
+        // bit i - 1 is set exactly when D[i][j] = D[i - 1][j - 1].
+        let d0 = eq
+            .bit_and(vp)
+            .wide_add(vp)
+            .bit_xor(vp)
+            .bit_or(eq)
+            .bit_or(vn);
+
+        // Move one column right.
+        let (hp, hn) = step(&mut vp, &mut vn, eq, true);
+
+        cols.push((vp, d0));
+
+        // Track the horizontal delta of the last pattern row. With an empty pattern, the last
+        // row is row 0, which grows by one per column.
+        //
+        // Safety: `Peq` guarantees that `m <= 8 * size_of::<T>()`, and `m > 0`.
+        if m > 0 {
+            score += unsafe { hp.bit_test_unchecked(m - 1) } as usize;
+            score -= unsafe { hn.bit_test_unchecked(m - 1) } as usize;
+        } else {
+            score += 1;
+        }
+
+        if score < best.0 {
+            best = (score, j);
+        }
+    }
+
+    let (distance, end) = match mode {
+        Mode::Global => (score, b.len()),
+        Mode::Prefix => best,
+    };
+
+    // Walk back from the end cell, collecting operations in reverse.
+    let mut ops = Vec::with_capacity(m + end);
+    let (mut i, mut j) = (m, end);
+
+    while i > 0 && j > 0 {
+        let (vp, d0) = cols[j - 1];
+
+        // Safety: `i - 1 < m <= 8 * size_of::<T>()`.
+        let op = unsafe {
+            if !d0.bit_test_unchecked(i - 1) {
+                // D[i][j] = D[i - 1][j - 1] + 1. A match would give a diagonal zero, so this is
+                // a mismatch.
+                b'X'
+            } else if peq.get(b[j - 1]).bit_test_unchecked(i - 1) {
+                b'='
+            } else if vp.bit_test_unchecked(i - 1) {
+                // A diagonal zero on a mismatch, so the cell is reached from above or the left.
+                // Here D[i][j] = D[i - 1][j] + 1.
+                b'I'
+            } else {
+                b'D'
+            }
+        };
+
+        match op {
+            b'I' => i -= 1,
+            b'D' => j -= 1,
+            _ => (i, j) = (i - 1, j - 1),
+        }
+
+        ops.push(op);
+    }
+
+    // Whatever is left of either string hangs off the edge of the DP matrix.
+    ops.extend(core::iter::repeat_n(b'I', i));
+    ops.extend(core::iter::repeat_n(b'D', j));
+
+    Alignment {
+        start: 0,
+        end,
+        distance,
+        cigar: cigar(&ops),
+    }
+}
+
+// Run-length encode reversed operations into a CIGAR string.
+fn cigar(ops: &[u8]) -> String {
+    let mut s = String::new();
+
+    for run in ops.chunk_by(|x, y| x == y).rev() {
+        // Writing to a `String` never fails.
+        let _ = write!(s, "{}{}", run.len(), run[0] as char);
+    }
+
+    s
+}
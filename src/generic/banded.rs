@@ -0,0 +1,139 @@
+use crate::generic::single::step;
+use crate::word::Word;
+
+/// Perform Hyyrö's banded Myers algorithm to find the edit distance between `a` and `b`, as long
+/// as it is at most `k`, using a single word of type `T`. Only the `2k + 1` diagonals of the DP
+/// matrix within `k` of the main diagonal are computed. The band slides one row down the word per
+/// column of `b`, so the cost is linear in `b.len()` however long `a` is. The band keeps its own
+/// per-symbol equality bits, so no `Peq` is needed. Each column shifts the bits of its symbol into
+/// place, and adds just the bit of the row entering the band. This is the kernel behind every
+/// `myers_ed_banded_*`.
+///
+/// Returns `None` if the distance is greater than `k`, and otherwise exactly the distance.
+///
+/// # Panics
+///
+/// Panics if the band doesn't fit in one word, i.e. if `2k + 1 > 8 * size_of::<T>()`.
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::generic::banded::myers_ed_banded;
+/// # fn main() {
+/// let a = [b'A'; 1000];
+/// let mut b = [b'A'; 1000];
+/// b[500] = b'C';
+///
+/// assert_eq!(myers_ed_banded::<u64>(&a, &b, 2), Some(1));
+/// assert_eq!(myers_ed_banded::<u64>(&a, &b[..990], 2), None);
+/// # }
+/// ```
+#[inline(always)]
+pub fn myers_ed_banded<T: Word>(a: &[u8], b: &[u8], k: usize) -> Option<usize> {
+    let w = 8 * size_of::<T>();
+    assert!(k < w / 2, "Band of width 2k + 1 must fit in one word");
+
+    let (m, n) = (a.len(), b.len());
+
+    // Every alignment needs at least |m - n| insertions or deletions.
+    if m.abs_diff(n) > k {
+        return None;
+    }
+
+    // Bit 2k is the bottom row of the band, and the mask of the rows above it.
+    //
+    // Safety: `k < w / 2`, so `2k < w`.
+    let bottom = unsafe { T::bit_at_unchecked(2 * k) };
+    let above_bottom = bottom.wide_add(T::ONES);
+
+    // Bit t of the band holds row off + t + 1. Up to column k + 1 the band still reaches up to
+    // row 1, so `off` is 0. Afterwards it is j - k - 1 in column j, moving down a row per column.
+    //
+    // Equality bits of each symbol for every row up to the bottom of the band, as they would be
+    // at offset `at[c]`, i.e. with bit t holding row at[c] + t + 1. Each column shifts a copy of
+    // the bits of its own symbol into place, and only ever writes one bit for the row entering the
+    // band. The bits of a symbol are only realigned when that row would not fit in the word.
+    let mut peq = [T::ZERO; 256];
+    let mut at = [0; 256];
+
+    // Right shift `x` by `d`, which may be the full width of the word or more.
+    #[inline(always)]
+    fn shr<T: Word>(x: T, d: usize) -> T {
+        // Safety: `d < 8 * size_of::<T>()` is checked first.
+        if d < 8 * size_of::<T>() {
+            unsafe { x.shr_unchecked(d) }
+        } else {
+            T::ZERO
+        }
+    }
+
+    // Rows 1 to 2k + 1 start in the band.
+    for (t, &c) in a.iter().take(2 * k + 1).enumerate() {
+        // Safety: `t <= 2k < w`.
+        peq[c as usize] = peq[c as usize].bit_or(unsafe { T::bit_at_unchecked(t) });
+    }
+
+    let mut vp = T::ONES;
+    let mut vn = T::ZERO;
+
+    // Value of the row just above bit 0. Row 0 while the band reaches up to row 1. Afterwards,
+    // the row above the band, whose horizontal delta is always taken to be +1. This can only
+    // overestimate it, which is harmless as the DP never prefers a path through it.
+    let mut above = 0;
+
+    for (j, &c) in (1_usize..).zip(b) {
+        let off = j.saturating_sub(k + 1);
+        let eq = shr(peq[c as usize], off - at[c as usize]);
+
+        above += 1;
+
+        if j <= k {
+            step(&mut vp, &mut vn, eq, true);
+            continue;
+        }
+
+        // From column k + 1 on, the band moves down a row after each column. Shifting the new
+        // vertical deltas right by one cancels the left shift of the horizontal deltas in `step`,
+        // so only `xv` is shifted.
+        let d0 = eq.bit_and(vp).wide_add(vp).bit_xor(vp).bit_or(eq);
+        let hp = vn.bit_or(vp.bit_or(d0).bit_not());
+        let hn = vp.bit_and(d0);
+        let xv = eq.bit_or(vn);
+        let xv_shr = xv.shr_one();
+
+        // The old top row becomes the row above the band. Its horizontal delta in is +1, so its
+        // vertical delta is -1 when `xv` is set, and otherwise 0.
+        //
+        // Safety: `0 < w`.
+        above -= unsafe { xv.bit_test_unchecked(0) } as usize;
+
+        // Give the new bottom row a vertical delta of +1 so the DP never prefers a path through
+        // the cell left of it, which is outside the band.
+        vp = hn.bit_or(xv_shr.bit_or(hp).bit_not()).bit_or(bottom);
+        vn = hp.bit_and(xv_shr).bit_and(above_bottom);
+
+        // Row r = off + 2k + 2 enters the band at the bottom. Its bit is r - 1 - at[x], so once
+        // that is past the top of the word, move the bits of `x` up to the new offset first.
+        if let Some(&x) = a.get(off + 2 * k + 1) {
+            let (eq, at) = (&mut peq[x as usize], &mut at[x as usize]);
+
+            if off + 2 * k + 1 - *at >= w {
+                *eq = shr(*eq, off + 1 - *at);
+                *at = off + 1;
+            }
+
+            // Safety: `off + 2k + 1 - at[x] < w`, checked above.
+            *eq = eq.bit_or(unsafe { T::bit_at_unchecked(off + 2 * k + 1 - *at) });
+        }
+    }
+
+    // Sum the vertical deltas from the row above bit 0 down to row m. The band ends at offset
+    // n - k once it has moved at all.
+    let rows = m - n.saturating_sub(k);
+
+    // Safety: `rows <= 2k + 1 <= w`, since `|m - n| <= k`.
+    let d = above + unsafe { vp.popcnt_upto_unchecked(rows) }
+        - unsafe { vn.popcnt_upto_unchecked(rows) };
+
+    (d <= k).then_some(d)
+}
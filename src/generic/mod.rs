@@ -0,0 +1,8 @@
+//! Kernels written once against the [`Word`](crate::word::Word) trait, and instantiated by each
+//! backend with its own word type.
+
+pub mod align;
+pub mod banded;
+pub mod multi;
+pub mod search;
+pub mod single;
@@ -0,0 +1,194 @@
+use crate::peq::MultiWordPeq;
+use crate::word::Word;
+
+/// Perform blocked Myers algorithm to find the edit distance between the string encoded in `peq`
+/// and `b`, using chains of words of type `T`. This is the kernel behind every
+/// `myers_ed_multi_*_with_peq`.
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::generic::multi::myers_ed_multi_with_peq;
+/// # use myers_ed::peq::MultiWordPeq;
+/// # fn main() {
+/// let a = [b'A'; 300];
+/// let mut b = [b'A'; 300];
+/// b[200] = b'C';
+///
+/// let peq = MultiWordPeq::<u128>::from_bytes(a);
+///
+/// assert_eq!(myers_ed_multi_with_peq(&peq, &b), 1);
+/// # }
+/// ```
+#[inline(always)]
+pub fn myers_ed_multi_with_peq<T: Word>(peq: &MultiWordPeq<T>, b: &[u8]) -> usize {
+    let w = 8 * size_of::<T>();
+    let blocks = peq.blocks();
+
+    // Vertical positive delta bit-vectors, one per block.
+    let mut vp = vec![T::ONES; blocks];
+
+    // Vertical negative delta bit-vectors, one per block.
+    let mut vn = vec![T::ZERO; blocks];
+
+    // Update loop.
+    for &x in b {
+        // Get the equality masks for the current character.
+        //
+        // Infallible: `x as usize` \in [0, 255] and `peq` stores 256 symbols.
+        let eqs = &peq[x as usize];
+
+        // Horizontal delta carried into the top of the first block. The top row of the DP
+        // matrix increases by one each column, so we always start with a positive delta.
+        let mut hp_in = true;
+        let mut hn_in = false;
+
+        for ((&eq, vp), vn) in eqs.iter().zip(vp.iter_mut()).zip(vn.iter_mut()) {
+            let (hp, hn) = step_block(vp, vn, eq, hp_in, hn_in);
+
+            // Horizontal deltas of this block's last row carry into the next block.
+            hp_in = hp.msb();
+            hn_in = hn.msb();
+        }
+    }
+
+    // Compute final edit distance.
+    let mut vp_popcnt = 0;
+    let mut vn_popcnt = 0;
+
+    for (i, (&vp, &vn)) in vp.iter().zip(vn.iter()).enumerate() {
+        // Number of real bits in this block. Every block except the last is full.
+        let n = (peq.len() - w * i).min(w);
+
+        // Safety: `n <= w` by construction.
+        vp_popcnt += unsafe { vp.popcnt_upto_unchecked(n) };
+        vn_popcnt += unsafe { vn.popcnt_upto_unchecked(n) };
+    }
+
+    b.len() + vp_popcnt - vn_popcnt
+}
+
+/// Perform blocked Myers algorithm like [`myers_ed_multi_with_peq`], but give up as soon as the
+/// edit distance is known to be greater than `k`. This is the kernel behind every
+/// `myers_ed_multi_*_bounded_with_peq`. Returns `None` once the score of the last pattern row
+/// exceeds `k` by more than the number of remaining columns of `b`, and otherwise exactly the
+/// distance of [`myers_ed_multi_with_peq`].
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::generic::multi::myers_ed_multi_bounded_with_peq;
+/// # use myers_ed::peq::MultiWordPeq;
+/// # fn main() {
+/// let a = [b'A'; 300];
+/// let mut b = [b'A'; 300];
+/// b[200] = b'C';
+///
+/// let peq = MultiWordPeq::<u128>::from_bytes(a);
+///
+/// assert_eq!(myers_ed_multi_bounded_with_peq(&peq, &b, 1), Some(1));
+/// assert_eq!(myers_ed_multi_bounded_with_peq(&peq, &[b'C'; 300], 10), None);
+/// # }
+/// ```
+#[inline(always)]
+pub fn myers_ed_multi_bounded_with_peq<T: Word>(
+    peq: &MultiWordPeq<T>,
+    b: &[u8],
+    k: usize,
+) -> Option<usize> {
+    let w = 8 * size_of::<T>();
+    let m = peq.len();
+
+    // With an empty pattern, there is no last row to track and the distance is just `b.len()`.
+    if m == 0 {
+        return (b.len() <= k).then_some(b.len());
+    }
+
+    let blocks = peq.blocks();
+
+    // The last pattern row is this bit of the last block.
+    let last = m - 1 - w * (blocks - 1);
+
+    let mut vp = vec![T::ONES; blocks];
+    let mut vn = vec![T::ZERO; blocks];
+
+    // Score of the last pattern row, starting from column 0.
+    let mut score = m;
+
+    // Update loop.
+    for (j, &x) in b.iter().enumerate() {
+        // Infallible: `x as usize` \in [0, 255] and `peq` stores 256 symbols.
+        let eqs = &peq[x as usize];
+
+        let mut hp_in = true;
+        let mut hn_in = false;
+
+        // Every block but the last only carries its horizontal deltas on.
+        for ((&eq, vp), vn) in eqs
+            .iter()
+            .zip(vp.iter_mut())
+            .zip(vn.iter_mut())
+            .take(blocks - 1)
+        {
+            let (hp, hn) = step_block(vp, vn, eq, hp_in, hn_in);
+
+            hp_in = hp.msb();
+            hn_in = hn.msb();
+        }
+
+        let (hp, hn) = step_block(
+            &mut vp[blocks - 1],
+            &mut vn[blocks - 1],
+            eqs[blocks - 1],
+            hp_in,
+            hn_in,
+        );
+
+        // Track the horizontal delta of the last pattern row.
+        //
+        // Safety: `last < w` by construction.
+        score += unsafe { hp.bit_test_unchecked(last) } as usize;
+        score -= unsafe { hn.bit_test_unchecked(last) } as usize;
+
+        // Stop once even a match in every remaining column can't bring the score down to `k`.
+        if score > k.saturating_add(b.len() - j - 1) {
+            return None;
+        }
+    }
+
+    (score <= k).then_some(score)
+}
+
+/// Move one block one column right in the DP matrix, updating its vertical deltas `vp` and `vn`
+/// with the equality mask `eq` and the horizontal deltas `hp_in` and `hn_in` carried in from the
+/// block above. Returns the horizontal deltas of the new column, before they are shifted.
+#[inline(always)]
+fn step_block<T: Word>(vp: &mut T, vn: &mut T, eq: T, hp_in: bool, hn_in: bool) -> (T, T) {
+    // Calculate intermediate mask for next column's vertical delta bits.
+    let xh = eq.bit_or(*vn);
+
+    // A negative horizontal delta coming in from the block above behaves like a match
+    // in the first row of this block, so fold it into the equality mask.
+    let eq = eq.bit_or(T::ZERO.shl_one(hn_in));
+
+    // Calculate diagonal zero delta bit-vector. This is d0 = (((eq & vp) + vp) ^ vp) | eq.
+    let d0 = eq.bit_and(*vp).wide_add(*vp).bit_xor(*vp).bit_or(eq);
+
+    // Calculate horizontal positive delta bit-vector. This is hp = vn | !(vp | d0).
+    let hp = vn.bit_or(vp.bit_or(d0).bit_not());
+
+    // Calculate horizontal negative delta bit-vector.
+    let hn = vp.bit_and(d0);
+
+    // Move one column right in DP matrix, shifting in the carried horizontal deltas.
+    let hp_shl = hp.shl_one(hp_in);
+    let hn_shl = hn.shl_one(hn_in);
+
+    // Update positive vertical delta bit-vector. This is hn | !(xh | hp).
+    *vp = hn_shl.bit_or(xh.bit_or(hp_shl).bit_not());
+
+    // Update negative vertical delta bit-vector.
+    *vn = hp_shl.bit_and(xh);
+
+    (hp, hn)
+}
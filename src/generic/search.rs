@@ -0,0 +1,101 @@
+use core::iter::FusedIterator;
+
+use crate::generic::single::step;
+use crate::peq::Peq;
+use crate::word::Word;
+
+/// Iterator over the approximate occurrences of the string encoded in a `Peq` in a text, returned
+/// by [`myers_ed_search_with_peq`]. Yields `(end_pos, distance)` in increasing `end_pos` order.
+pub struct Search<'a, T, P> {
+    peq: &'a P,
+    text: &'a [u8],
+    k: usize,
+    vp: T,
+    vn: T,
+    // Score of the last pattern row at column `j`.
+    score: usize,
+    // Number of columns of `text` consumed so far, or `None` before column 0 has been reported.
+    j: Option<usize>,
+}
+
+/// Perform Myers algorithm in search mode to find every occurrence of the string encoded in `peq`
+/// in `text` with at most `k` errors, using a single word of type `T`. This is the kernel behind
+/// every `myers_ed_search_*_with_peq`.
+///
+/// The start of `text` is free, i.e. the horizontal boundary of the DP matrix is zero, so a match
+/// may start anywhere. Yields `(end_pos, distance)` for every `end_pos` in `0..=text.len()` such
+/// that some substring `text[start..end_pos]` is within edit distance `distance <= k` of the
+/// pattern, with `distance` the smallest such distance.
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::generic::search::myers_ed_search_with_peq;
+/// # use myers_ed::peq::SingleWordPeq;
+/// # fn main() {
+/// let peq = SingleWordPeq::<u64>::from_bytes(b"ACGT");
+/// let hits: Vec<_> = myers_ed_search_with_peq(&peq, b"TTACGTTTAGTT", 1).collect();
+///
+/// assert_eq!(hits, [(5, 1), (6, 0), (7, 1), (11, 1)]);
+/// # }
+/// ```
+#[inline(always)]
+pub fn myers_ed_search_with_peq<'a, T: Word, P: Peq<T>>(
+    peq: &'a P,
+    text: &'a [u8],
+    k: usize,
+) -> Search<'a, T, P> {
+    Search {
+        peq,
+        text,
+        k,
+        vp: T::ONES,
+        vn: T::ZERO,
+        score: peq.len(),
+        j: None,
+    }
+}
+
+impl<T: Word, P: Peq<T>> Iterator for Search<'_, T, P> {
+    type Item = (usize, usize);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<(usize, usize)> {
+        // Column 0 matches the empty substring, at a distance of the pattern length.
+        let Some(j) = self.j.as_mut() else {
+            self.j = Some(0);
+
+            if self.score <= self.k {
+                return Some((0, self.score));
+            }
+
+            return self.next();
+        };
+
+        let m = self.peq.len();
+
+        while let Some(&x) = self.text.get(*j) {
+            *j += 1;
+
+            // Get the equality mask for the current character, and move one column right. Row 0
+            // is zero in every column, so its horizontal delta is zero.
+            let (hp, hn) = step(&mut self.vp, &mut self.vn, self.peq.get(x), false);
+
+            // Track the horizontal delta of the last pattern row. An empty pattern always matches.
+            //
+            // Safety: `Peq` guarantees that `m <= 8 * size_of::<T>()`, and `m > 0`.
+            if m > 0 {
+                self.score += unsafe { hp.bit_test_unchecked(m - 1) } as usize;
+                self.score -= unsafe { hn.bit_test_unchecked(m - 1) } as usize;
+            }
+
+            if self.score <= self.k {
+                return Some((*j, self.score));
+            }
+        }
+
+        None
+    }
+}
+
+impl<T: Word, P: Peq<T>> FusedIterator for Search<'_, T, P> {}
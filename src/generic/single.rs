@@ -0,0 +1,192 @@
+use crate::mode::Mode;
+use crate::peq::Peq;
+use crate::word::Word;
+
+/// Perform Myers algorithm to find the edit distance between the string encoded in `peq` and `b`,
+/// using a single word of type `T`. This is the kernel behind every `myers_ed_single_*_with_peq`.
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::generic::single::myers_ed_single_with_peq;
+/// # use myers_ed::peq::SingleWordPeq;
+/// # fn main() {
+/// let peq = SingleWordPeq::<u64>::from_bytes(b"ACCC");
+///
+/// assert_eq!(myers_ed_single_with_peq(&peq, b"ACCT"), 1);
+/// # }
+/// ```
+#[inline(always)]
+pub fn myers_ed_single_with_peq<T: Word, P: Peq<T>>(peq: &P, b: &[u8]) -> usize {
+    // Vertical positive delta bit-vector.
+    let mut vp = T::ONES;
+
+    // Vertical negative delta bit-vector.
+    let mut vn = T::ZERO;
+
+    // Update loop.
+    for &x in b {
+        // Get the equality mask for the current character, and move one column right.
+        step(&mut vp, &mut vn, peq.get(x), true);
+    }
+
+    // Compute final edit distance, using only the real bits.
+    //
+    // Safety: `Peq` guarantees that `peq.len() <= 8 * size_of::<T>()`.
+    let vp_popcnt = unsafe { vp.popcnt_upto_unchecked(peq.len()) };
+    let vn_popcnt = unsafe { vn.popcnt_upto_unchecked(peq.len()) };
+
+    b.len() + vp_popcnt - vn_popcnt
+}
+
+/// Perform Myers algorithm like [`myers_ed_single_with_peq`], but give up as soon as the edit
+/// distance is known to be greater than `k`. This is the kernel behind every
+/// `myers_ed_single_*_bounded_with_peq`.
+///
+/// The score of the last pattern row can drop by at most one per column, so once it exceeds `k`
+/// by more than the number of remaining columns of `b`, the distance must exceed `k`. Returns
+/// `None` in that case, and otherwise exactly the distance of [`myers_ed_single_with_peq`].
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::generic::single::myers_ed_single_bounded_with_peq;
+/// # use myers_ed::peq::SingleWordPeq;
+/// # fn main() {
+/// let peq = SingleWordPeq::<u64>::from_bytes(b"ACCC");
+///
+/// assert_eq!(myers_ed_single_bounded_with_peq(&peq, b"ACCT", 1), Some(1));
+/// assert_eq!(myers_ed_single_bounded_with_peq(&peq, b"TTTTTTTT", 3), None);
+/// # }
+/// ```
+#[inline(always)]
+pub fn myers_ed_single_bounded_with_peq<T: Word, P: Peq<T>>(
+    peq: &P,
+    b: &[u8],
+    k: usize,
+) -> Option<usize> {
+    let m = peq.len();
+
+    // With an empty pattern, there is no last row to track and the distance is just `b.len()`.
+    if m == 0 {
+        return (b.len() <= k).then_some(b.len());
+    }
+
+    let mut vp = T::ONES;
+    let mut vn = T::ZERO;
+
+    // Score of the last pattern row, starting from column 0.
+    let mut score = m;
+
+    // Update loop.
+    for (j, &x) in b.iter().enumerate() {
+        // Get the equality mask for the current character, and move one column right.
+        let (hp, hn) = step(&mut vp, &mut vn, peq.get(x), true);
+
+        // Track the horizontal delta of the last pattern row.
+        //
+        // Safety: `Peq` guarantees that `m <= 8 * size_of::<T>()`, and `m > 0`.
+        score += unsafe { hp.bit_test_unchecked(m - 1) } as usize;
+        score -= unsafe { hn.bit_test_unchecked(m - 1) } as usize;
+
+        // Stop once even a match in every remaining column can't bring the score down to `k`.
+        if score > k.saturating_add(b.len() - j - 1) {
+            return None;
+        }
+    }
+
+    (score <= k).then_some(score)
+}
+
+/// Perform Myers algorithm like [`myers_ed_single_with_peq`], aligning the string encoded in `peq`
+/// to `b` as `mode` says. This is the kernel behind every `myers_ed_single_*_with_peq_and_mode`.
+///
+/// Returns `(distance, end)`, where `b[..end]` is the part of `b` the pattern was aligned to. For
+/// [`Mode::Global`] this is always all of `b`. For [`Mode::Prefix`], `distance` is the minimum
+/// score of the last pattern row over all columns, and `end` the first column that reaches it.
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::generic::single::myers_ed_single_with_peq_and_mode;
+/// # use myers_ed::mode::Mode;
+/// # use myers_ed::peq::SingleWordPeq;
+/// # fn main() {
+/// let peq = SingleWordPeq::<u64>::from_bytes(b"ACCC");
+///
+/// assert_eq!(myers_ed_single_with_peq_and_mode(&peq, b"ACGCTTTT", Mode::Global), (5, 8));
+/// assert_eq!(myers_ed_single_with_peq_and_mode(&peq, b"ACGCTTTT", Mode::Prefix), (1, 4));
+/// # }
+/// ```
+#[inline(always)]
+pub fn myers_ed_single_with_peq_and_mode<T: Word, P: Peq<T>>(
+    peq: &P,
+    b: &[u8],
+    mode: Mode,
+) -> (usize, usize) {
+    let m = peq.len();
+
+    if mode == Mode::Global {
+        return (myers_ed_single_with_peq(peq, b), b.len());
+    }
+
+    let mut vp = T::ONES;
+    let mut vn = T::ZERO;
+
+    // Score of the last pattern row, and the best score so far with the column it was reached in.
+    let mut score = m;
+    let mut best = (m, 0);
+
+    // An empty pattern matches the empty prefix, and no column can beat that.
+    if m == 0 {
+        return best;
+    }
+
+    // Update loop.
+    for (j, &x) in (1..).zip(b) {
+        // Get the equality mask for the current character, and move one column right.
+        let (hp, hn) = step(&mut vp, &mut vn, peq.get(x), true);
+
+        // Track the horizontal delta of the last pattern row.
+        //
+        // Safety: `Peq` guarantees that `m <= 8 * size_of::<T>()`, and `m > 0`.
+        score += unsafe { hp.bit_test_unchecked(m - 1) } as usize;
+        score -= unsafe { hn.bit_test_unchecked(m - 1) } as usize;
+
+        if score < best.0 {
+            best = (score, j);
+        }
+    }
+
+    best
+}
+
+/// Move one column right in the DP matrix, updating the vertical deltas `vp` and `vn` with the
+/// equality mask `eq`, and shifting in `hp_in` as the horizontal delta of row 0. This is `true`
+/// for global distance, and `false` when the start of `b` is free. Returns the horizontal deltas
+/// of the new column, before they are shifted.
+#[inline(always)]
+pub(crate) fn step<T: Word>(vp: &mut T, vn: &mut T, eq: T, hp_in: bool) -> (T, T) {
+    // Calculate diagonal zero delta bit-vector. This is d0 = (((eq & vp) + vp) ^ vp) | eq.
+    let d0 = eq.bit_and(*vp).wide_add(*vp).bit_xor(*vp).bit_or(eq);
+
+    // Calculate horizontal positive delta bit-vector. This is hp = vn | !(vp | d0).
+    let hp = vn.bit_or(vp.bit_or(d0).bit_not());
+
+    // Calculate horizontal negative delta bit-vector.
+    let hn = vp.bit_and(d0);
+
+    // Calculate intermediate mask for next column's vertical delta bits.
+    let xh = eq.bit_or(*vn);
+
+    // Move one column right in DP matrix. This is hp = (hp << 1) | hp_in.
+    let hp_shl = hp.shl_one(hp_in);
+
+    // Update positive vertical delta bit-vector. This is (hn << 1) | !(xh | hp).
+    *vp = hn.shl_one(false).bit_or(xh.bit_or(hp_shl).bit_not());
+
+    // Update negative vertical delta bit-vector.
+    *vn = hp_shl.bit_and(xh);
+
+    (hp, hn)
+}
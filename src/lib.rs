@@ -1,17 +1,43 @@
 #![warn(clippy::all, clippy::style, clippy::correctness)]
-#![allow(unsafe_op_in_unsafe_fn, internal_features, incomplete_features)]
+#![allow(
+    unsafe_op_in_unsafe_fn,
+    internal_features,
+    incomplete_features,
+    unused_features
+)]
 #![feature(
-    unchecked_shifts,
     exact_div,
     likely_unlikely,
     generic_const_exprs,
     const_trait_impl,
     default_field_values
 )]
+#![cfg_attr(feature = "portable-simd", feature(portable_simd))]
 
+// Without their crate features, the AVX modules are private and only reached through runtime
+// dispatch. Their doctests cannot reach them there, so skip them when collecting doctests.
+#[cfg(feature = "avx2")]
+pub mod avx2;
+#[cfg(all(not(feature = "avx2"), not(doctest)))]
+mod avx2;
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
 pub mod avx512;
+#[cfg(all(
+    not(feature = "avx512"),
+    not(feature = "avx512-emulated"),
+    not(doctest)
+))]
+mod avx512;
+pub mod dispatch;
+pub mod generic;
+pub mod mode;
 pub mod peq;
+pub mod portable;
 pub mod prelude;
 pub mod scalar;
+pub mod sse;
+pub mod word;
 
 pub(crate) mod macros;
+
+pub use dispatch::{myers_ed, myers_ed_with_peq, try_myers_ed};
@@ -30,3 +30,94 @@ macro_rules! assert_target_features {
         };
     };
 }
+
+/// Implement [`Word`](crate::word::Word) for a SIMD type by forwarding every operation to its
+/// [`Unchecked`](crate::word::Unchecked) implementation. Only sound where the target features of
+/// that implementation are asserted at compile time, i.e. behind the matching crate feature.
+/// Expects [`Word`](crate::word::Word) and [`Unchecked`](crate::word::Unchecked) in scope, and
+/// the `Spread` type spelled out since the crate-private one cannot be named in a public impl.
+#[cfg(any(feature = "avx2", feature = "avx512", feature = "avx512-emulated"))]
+macro_rules! forward_word {
+    ($t:ty, $spread:ty) => {
+        impl Word for $t {
+            const ZERO: $t = <Unchecked<$t> as Word>::ZERO.0;
+            const ONE: $t = <Unchecked<$t> as Word>::ONE.0;
+            const ONES: $t = <Unchecked<$t> as Word>::ONES.0;
+
+            #[inline(always)]
+            unsafe fn bit_at_unchecked(i: usize) -> $t {
+                <Unchecked<$t> as Word>::bit_at_unchecked(i).0
+            }
+
+            #[inline(always)]
+            unsafe fn bit_test_unchecked(self, i: usize) -> bool {
+                Word::bit_test_unchecked(Unchecked(self), i)
+            }
+
+            #[inline(always)]
+            fn bit_or(self, rhs: $t) -> $t {
+                Word::bit_or(Unchecked(self), Unchecked(rhs)).0
+            }
+
+            #[inline(always)]
+            fn bit_and(self, rhs: $t) -> $t {
+                Word::bit_and(Unchecked(self), Unchecked(rhs)).0
+            }
+
+            #[inline(always)]
+            fn bit_xor(self, rhs: $t) -> $t {
+                Word::bit_xor(Unchecked(self), Unchecked(rhs)).0
+            }
+
+            #[inline(always)]
+            fn bit_not(self) -> $t {
+                Word::bit_not(Unchecked(self)).0
+            }
+
+            #[inline(always)]
+            fn wide_add(self, rhs: $t) -> $t {
+                Word::wide_add(Unchecked(self), Unchecked(rhs)).0
+            }
+
+            #[inline(always)]
+            fn shl_one(self, carry: bool) -> $t {
+                Word::shl_one(Unchecked(self), carry).0
+            }
+
+            #[inline(always)]
+            fn shr_one(self) -> $t {
+                Word::shr_one(Unchecked(self)).0
+            }
+
+            #[inline(always)]
+            unsafe fn shr_unchecked(self, n: usize) -> $t {
+                Word::shr_unchecked(Unchecked(self), n).0
+            }
+
+            #[inline(always)]
+            fn msb(self) -> bool {
+                Word::msb(Unchecked(self))
+            }
+
+            #[inline(always)]
+            unsafe fn popcnt_upto_unchecked(self, n: usize) -> usize {
+                Word::popcnt_upto_unchecked(Unchecked(self), n)
+            }
+
+            type Spread = $spread;
+
+            #[inline(always)]
+            unsafe fn spread_bytes_unchecked(s: &[u8]) -> $spread {
+                <Unchecked<$t> as Word>::spread_bytes_unchecked(s)
+            }
+
+            #[inline(always)]
+            fn eq_mask(spread: &$spread, c: u8) -> $t {
+                <Unchecked<$t> as Word>::eq_mask(spread, c).0
+            }
+        }
+    };
+}
+
+#[cfg(any(feature = "avx2", feature = "avx512", feature = "avx512-emulated"))]
+pub(crate) use forward_word;
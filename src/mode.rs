@@ -0,0 +1,12 @@
+//! Alignment modes, which decide which ends of the text are free.
+
+/// Alignment modes for the single-word `*_with_mode` kernels.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum Mode {
+    /// Global (NW) distance. The whole pattern is aligned to the whole text.
+    #[default]
+    Global,
+    /// Prefix (SHW) distance. The whole pattern is aligned to the best-matching prefix of the
+    /// text, so the end of the text is free.
+    Prefix,
+}
@@ -1,64 +1,26 @@
-use core::arch::x86_64::*;
 use core::mem::size_of;
 use core::ops::Index;
 
-pub trait Word {
-    /// This word entirely zeroed.
-    const ZERO: Self;
+use anyhow::{Result, anyhow};
 
-    /// This word's LSB set to 1 and the rest zeroed.
-    const ONE: Self;
+pub use crate::word::Word;
 
-    /// Compute a bitmask such that bit `i` is set to 1 and the rest of the word is zeroed.
-    ///
-    /// # Safety
-    ///
-    /// Caller must guarantee that `i < 8 * std::mem::size_of::<Self>()`.
-    unsafe fn bit_at_unchecked(i: usize) -> Self;
-
-    /// Compute the bitwise OR operation between `self` and `rhs`. Equivalent to
-    /// [`BitOr`](`std::ops::BitOr`) but has custom implementations in this crate for SIMD types.
-    fn bit_or(self, rhs: Self) -> Self;
-}
+/// Lookup of single-word equality masks, shared by every single-word Peq layout so the kernels
+/// can accept any of them.
+///
+/// # Safety
+///
+/// Implementors must guarantee that `len() <= 8 * std::mem::size_of::<T>()`, since the kernels
+/// rely on it to build their final masks.
+pub unsafe trait Peq<T> {
+    /// Equality mask of symbol `c`, with bit `i` set if the `i`-th byte of the string is `c`.
+    fn get(&self, c: u8) -> T;
 
-impl Word for u64 {
-    const ZERO: u64 = 0;
-    const ONE: u64 = 1;
+    /// Length of the string encoded in this Peq.
+    fn len(&self) -> usize;
 
-    #[inline(always)]
-    unsafe fn bit_at_unchecked(i: usize) -> u64 {
-        1_u64 << i
-    }
-
-    #[inline(always)]
-    fn bit_or(self, rhs: u64) -> u64 {
-        self | rhs
-    }
-}
-
-#[cfg(feature = "avx512")]
-impl Word for __m512i {
-    // Safety: `__m512i` is POD so this value is valid.
-    const ZERO: __m512i = unsafe { core::mem::zeroed() };
-
-    // Safety: `__m512i` has an exact underlying representation of `[i64; 8]`.
-    const ONE: __m512i = unsafe { core::mem::transmute::<[i64; 8], _>([1, 0, 0, 0, 0, 0, 0, 0]) };
-
-    #[inline(always)]
-    unsafe fn bit_at_unchecked(i: usize) -> __m512i {
-        // Mask for lane containing bit to set.
-        let k = 1_u8 << (i >> 6);
-
-        // Set bit inside selected lane.
-        let a = 1_i64 << (i & 63);
-
-        _mm512_mask_set1_epi64(Self::ZERO, k, a)
-    }
-
-    #[inline(always)]
-    fn bit_or(self, rhs: __m512i) -> __m512i {
-        // Safety: we guarantee that avx512f is present if avx512 crate feature compiles.
-        unsafe { _mm512_or_si512(self, rhs) }
+    fn is_empty(&self) -> bool {
+        self.len() == 0
     }
 }
 
@@ -68,10 +30,12 @@ pub struct SingleWordPeq<T> {
     len: usize,
 }
 
-impl<T: Word + Copy> SingleWordPeq<T> {
+impl<T: Word> SingleWordPeq<T> {
     pub fn from_bytes<B: AsRef<[u8]>>(s: B) -> SingleWordPeq<T> {
-        let mut peq = Self::default();
-        peq.len = s.as_ref().len();
+        let mut peq = Self {
+            len: s.as_ref().len(),
+            ..Default::default()
+        };
 
         assert!(
             peq.len <= 8 * size_of::<T>(),
@@ -89,27 +53,75 @@ impl<T: Word + Copy> SingleWordPeq<T> {
         peq
     }
 
+    /// Build a Peq for `s` by comparing the whole string against each symbol of `a` at once,
+    /// rather than visiting each byte of `s`. This is `O(alphabet_size)` SIMD compares, which is
+    /// much faster than [`SingleWordPeq::from_bytes`] for small alphabets. Bytes of `s` that are
+    /// not in `a` never match anything.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use myers_ed::peq::{BitAlphabet, SingleWordPeq};
+    /// # use myers_ed::scalar::single::myers_ed_single_scalar_with_peq;
+    /// # fn main() {
+    /// let peq = SingleWordPeq::from_bytes_and_alphabet(b"GATTACA", BitAlphabet::from_bytes(b"ACGT"));
+    ///
+    /// assert_eq!(peq[b'A' as usize], 0b1010010);
+    /// assert_eq!(myers_ed_single_scalar_with_peq(&peq, b"GATACA"), 1);
+    /// # }
+    /// ```
     pub fn from_bytes_and_alphabet<B: AsRef<[u8]>>(s: B, a: BitAlphabet) -> SingleWordPeq<T> {
-        let mut peq = Self::default();
-
-        // TODO: So probably need to just call a Word trait here. Because we want different
-        // implementations depending on ISA. Scalar will just iterate over symbols, iterate
-        // over string, set bit in peq. AVX-512 will iterate over symbol, spread chars into
-        // lanes, check if char == symbol and return mask, then OR that mask directly into
-        // the peq (frame adjusted ofc). AVX-512 can handle 64x8-bit lanes, so pretty stupid
-        // fast peq construction here. The bigger string should always be put inside the peq.
-        // SIMD construction is O(alphabet_size + alphabet_size * ceil(n / w)), which reduces
-        // to O(alphabet_size * ceil(n / w)). For single-word, n < w, so this reduces further
-        // to O(alphabet_size). A 512-bit DNA string can be handled in 4 iterations. Each
-        // iteration contains a load, a maskz_cmp, and an OR. Previous implementation was O(n)
-        // and did way more work per iter. This is probably now only a few ns.
+        let s = s.as_ref();
+
+        assert!(
+            s.len() <= 8 * size_of::<T>(),
+            "Input byte array must be smaller than {} bytes",
+            8 * size_of::<T>()
+        );
+
+        let mut peq = Self {
+            len: s.len(),
+            ..Default::default()
+        };
+
+        // Safety: we've verified `s.len() <= 8 * size_of::<T>()`.
+        let spread = unsafe { T::spread_bytes_unchecked(s) };
+
+        // Compare the spread string against every symbol and store the resulting mask.
         //
-        // Could always add a `const`
-        // version of BitAlphabet that knows its length at compile-time, so we can unroll
-        // this loop exactly (if size is small). Can add the `const` using a `const` generic
-        // special method in the builder.
+        // Could always add a `const` version of BitAlphabet that knows its length at compile-time,
+        // so we can unroll this loop exactly (if size is small).
+        for c in a.iter() {
+            peq[c as usize] = T::eq_mask(&spread, c);
+        }
+
+        peq
+    }
+
+    /// Rebuild this Peq in place for `s`, where `prev` are the bytes it was last built from. Only
+    /// the words of symbols in `prev` need clearing, rather than all 256 of them, which makes this
+    /// much cheaper than [`SingleWordPeq::from_bytes`] for short inputs.
+    #[cfg(feature = "avx512")]
+    pub(crate) fn rebuild_from_bytes(&mut self, prev: &[u8], s: &[u8]) {
+        debug_assert_eq!(prev.len(), self.len);
+
+        assert!(
+            s.len() <= 8 * size_of::<T>(),
+            "Input byte array must be smaller than {} bytes",
+            8 * size_of::<T>()
+        );
+
+        for &x in prev {
+            self[x as usize] = T::ZERO;
+        }
+
+        // Encode the position of each character in the relevant mask.
+        for (i, &x) in s.iter().enumerate() {
+            // Safety: `i < s.len() <= 8 * size_of::<T>()` as required by function.
+            self[x as usize] = unsafe { self[x as usize].bit_or(T::bit_at_unchecked(i)) };
+        }
 
-        todo!()
+        self.len = s.len();
     }
 
     pub fn len(&self) -> usize {
@@ -151,6 +163,201 @@ impl<T: Word> Default for SingleWordPeq<T> {
     }
 }
 
+// Safety: every constructor asserts that `len <= 8 * size_of::<T>()`.
+unsafe impl<T: Copy> Peq<T> for SingleWordPeq<T> {
+    #[inline(always)]
+    fn get(&self, c: u8) -> T {
+        // Infallible: `c as usize` \in [0, 255] and `self.peq.len() == 256`.
+        self.peq[c as usize]
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+/// Single-word Peq that only stores a word for each symbol in its alphabet, plus a shared zero
+/// word for everything else. A 256-byte remap table translates each symbol to its slot. For a
+/// DNA alphabet this shrinks a `SingleWordPeq<__m512i>` from 16 KiB to under 600 bytes.
+pub struct CompactPeq<T> {
+    remap: [u8; 256],
+    peq: Box<[T]>,
+    len: usize,
+}
+
+impl<T: Word> CompactPeq<T> {
+    /// Build a compact Peq for `s`, using the distinct bytes of `s` as the alphabet.
+    pub fn from_bytes<B: AsRef<[u8]>>(s: B) -> CompactPeq<T> {
+        let s = s.as_ref();
+
+        Self::from_bytes_and_alphabet(s, BitAlphabet::from_bytes(s))
+    }
+
+    /// Build a compact Peq for `s` with one slot for each symbol of `a`. Bytes of `s` that are
+    /// not in `a` never match anything.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use myers_ed::peq::{BitAlphabet, CompactPeq};
+    /// # use myers_ed::scalar::single::myers_ed_single_scalar_with_peq;
+    /// # fn main() {
+    /// let peq = CompactPeq::<u64>::from_bytes_and_alphabet(b"GATTACA", BitAlphabet::DNA);
+    ///
+    /// assert_eq!(peq.slots(), 5);
+    /// assert_eq!(myers_ed_single_scalar_with_peq(&peq, b"GATACA"), 1);
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `s` is longer than `8 * size_of::<T>()` bytes, or if `a` contains all 256
+    /// symbols (which leaves no room for the zero slot; use a [`SingleWordPeq`] instead).
+    pub fn from_bytes_and_alphabet<B: AsRef<[u8]>>(s: B, a: BitAlphabet) -> CompactPeq<T> {
+        let s = s.as_ref();
+
+        assert!(
+            s.len() <= 8 * size_of::<T>(),
+            "Input byte array must be smaller than {} bytes",
+            8 * size_of::<T>()
+        );
+        assert!(a.len() < 256, "Alphabet must have fewer than 256 symbols");
+
+        // Slot 0 is the shared zero word for symbols outside the alphabet.
+        let mut remap = [0_u8; 256];
+        let mut peq = vec![T::ZERO; a.len() + 1].into_boxed_slice();
+
+        // Safety: we've verified `s.len() <= 8 * size_of::<T>()`.
+        let spread = unsafe { T::spread_bytes_unchecked(s) };
+
+        for (slot, c) in a.iter().enumerate() {
+            // Infallible: `slot + 1 <= a.len() < 256`.
+            remap[c as usize] = (slot + 1) as u8;
+            peq[slot + 1] = T::eq_mask(&spread, c);
+        }
+
+        CompactPeq {
+            remap,
+            peq,
+            len: s.len(),
+        }
+    }
+
+    /// Number of words stored, including the shared zero slot.
+    pub fn slots(&self) -> usize {
+        self.peq.len()
+    }
+}
+
+// Safety: every constructor asserts that `len <= 8 * size_of::<T>()`.
+unsafe impl<T: Copy> Peq<T> for CompactPeq<T> {
+    #[inline(always)]
+    fn get(&self, c: u8) -> T {
+        // Safety: `remap` only ever holds slots `0..=a.len()`, all of which are allocated.
+        unsafe { *self.peq.get_unchecked(self.remap[c as usize] as usize) }
+    }
+
+    #[inline(always)]
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+/// Peq for patterns spanning multiple words. Stores `ceil(len / (8 * size_of::<T>()))` blocks
+/// per symbol, laid out contiguously so a column update walks a single slice.
+pub struct MultiWordPeq<T> {
+    peq: Vec<T>,
+    blocks: usize,
+    len: usize,
+}
+
+impl<T: Word> MultiWordPeq<T> {
+    pub fn from_bytes<B: AsRef<[u8]>>(s: B) -> MultiWordPeq<T> {
+        // Infallible except on allocation failure, which aborts like any other `Vec` would.
+        Self::try_from_bytes(s).expect("Failed to allocate Peq")
+    }
+
+    pub fn try_from_bytes<B: AsRef<[u8]>>(s: B) -> Result<MultiWordPeq<T>> {
+        let s = s.as_ref();
+        let mut peq = Self::try_zeroed(s.len())?;
+
+        // Number of bits in a single block.
+        let w = 8 * size_of::<T>();
+
+        // Encode the position of each character in the relevant block's mask.
+        for (i, &x) in s.iter().enumerate() {
+            let idx = (x as usize) * peq.blocks + i / w;
+
+            // Safety: `i % w < w = 8 * size_of::<T>()` as required by function.
+            peq.peq[idx] = unsafe { peq.peq[idx].bit_or(T::bit_at_unchecked(i % w)) };
+        }
+
+        Ok(peq)
+    }
+
+    /// Build a Peq for `s` by comparing each block of the string against each symbol of `a`.
+    /// See [`SingleWordPeq::from_bytes_and_alphabet`].
+    pub fn from_bytes_and_alphabet<B: AsRef<[u8]>>(s: B, a: BitAlphabet) -> MultiWordPeq<T> {
+        // Infallible except on allocation failure, which aborts like any other `Vec` would.
+        Self::try_from_bytes_and_alphabet(s, a).expect("Failed to allocate Peq")
+    }
+
+    pub fn try_from_bytes_and_alphabet<B: AsRef<[u8]>>(
+        s: B,
+        a: BitAlphabet,
+    ) -> Result<MultiWordPeq<T>> {
+        let s = s.as_ref();
+        let mut peq = Self::try_zeroed(s.len())?;
+
+        for (b, chunk) in s.chunks(8 * size_of::<T>()).enumerate() {
+            // Safety: `chunks` guarantees `chunk.len() <= 8 * size_of::<T>()`.
+            let spread = unsafe { T::spread_bytes_unchecked(chunk) };
+
+            // Compare the spread block against every symbol and store the resulting mask.
+            for c in a.iter() {
+                peq.peq[(c as usize) * peq.blocks + b] = T::eq_mask(&spread, c);
+            }
+        }
+
+        Ok(peq)
+    }
+
+    /// Allocate a Peq for a string of `len` bytes with every block zeroed.
+    fn try_zeroed(len: usize) -> Result<MultiWordPeq<T>> {
+        let blocks = len.div_ceil(8 * size_of::<T>());
+
+        let mut peq = Vec::new();
+        peq.try_reserve_exact(256 * blocks)
+            .map_err(|e| anyhow!("Failed to allocate Peq of {} blocks: {}", blocks, e))?;
+        peq.resize(256 * blocks, T::ZERO);
+
+        Ok(MultiWordPeq { peq, blocks, len })
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of blocks stored per symbol.
+    pub fn blocks(&self) -> usize {
+        self.blocks
+    }
+}
+
+impl<T> Index<usize> for MultiWordPeq<T> {
+    type Output = [T];
+
+    /// Get all blocks for symbol `idx`, ordered from least to most significant.
+    fn index(&self, idx: usize) -> &Self::Output {
+        &self.peq[idx * self.blocks..(idx + 1) * self.blocks]
+    }
+}
+
 const trait AssertTrue<const B: bool, const E: usize> {
     const ERR_MSGS: [&str; 2] = [
         // Error message 0.
@@ -167,6 +374,23 @@ const trait AssertTrue<const B: bool, const E: usize> {
 
 impl<const B: bool, const T: usize> AssertTrue<B, T> for () {}
 
+/// Typestate builder for [`SingleWordPeq`] and [`MultiWordPeq`]. `STRING_CHECK` and
+/// `ALPHABET_CHECK` record whether a string and an alphabet have been provided, and the `build_*`
+/// finalisers only compile once `STRING_CHECK` is set. Builders start empty, from
+/// `PeqBuilder::<false, false>::default()`.
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::peq::PeqBuilder;
+/// # fn main() {
+/// let peq = PeqBuilder::<false, false>::default()
+///     .with_string("GATTACA")
+///     .build_single::<u64>();
+///
+/// assert_eq!(peq.len(), 7);
+/// # }
+/// ```
 #[derive(Default)]
 pub struct PeqBuilder<const STRING_CHECK: bool = false, const ALPHABET_CHECK: bool = false> {
     string: Option<Vec<u8>>,
@@ -176,6 +400,8 @@ pub struct PeqBuilder<const STRING_CHECK: bool = false, const ALPHABET_CHECK: bo
 impl<const STRING_CHECK: bool, const ALPHABET_CHECK: bool>
     PeqBuilder<STRING_CHECK, ALPHABET_CHECK>
 {
+    /// Create a builder holding `string` and `alphabet`. The `build_*` finalisers panic if
+    /// `STRING_CHECK` is set without a `string`.
     pub fn new(
         string: Option<Vec<u8>>,
         alphabet: Option<BitAlphabet>,
@@ -192,47 +418,277 @@ impl<const STRING_CHECK: bool, const ALPHABET_CHECK: bool>
 
     #[inline(always)]
     pub fn with_alphabet<S: AsRef<[u8]>>(mut self, alphabet: S) -> PeqBuilder<STRING_CHECK, true> {
-        let mut a = [0_u64; 4];
+        self.alphabet = Some(BitAlphabet::from_bytes(alphabet));
 
-        for c in alphabet.as_ref() {
-            a[(c >> 6) as usize] = 1 << (c & 63);
-        }
+        PeqBuilder::<STRING_CHECK, true>::new(self.string, self.alphabet)
+    }
 
-        self.alphabet = Some(BitAlphabet::new(a));
+    /// Use an existing [`BitAlphabet`], e.g. a preset or one shared between many builders.
+    #[inline(always)]
+    pub fn with_bit_alphabet(mut self, alphabet: BitAlphabet) -> PeqBuilder<STRING_CHECK, true> {
+        self.alphabet = Some(alphabet);
 
         PeqBuilder::<STRING_CHECK, true>::new(self.string, self.alphabet)
     }
 
+    /// Build a [`SingleWordPeq`] from the provided string. If no alphabet was provided, then one
+    /// is inferred from the string. Only compiles once a string has been added with
+    /// [`PeqBuilder::with_string`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use myers_ed::peq::PeqBuilder;
+    /// # use myers_ed::scalar::single::myers_ed_single_scalar_with_peq;
+    /// # fn main() {
+    /// let peq = PeqBuilder::<false, false>::default()
+    ///     .with_string("GATTACA")
+    ///     .with_alphabet("ACGT")
+    ///     .build_single::<u64>();
+    ///
+    /// assert_eq!(myers_ed_single_scalar_with_peq(&peq, b"GATACA"), 1);
+    /// # }
+    /// ```
+    ///
+    /// Building without a string is a compile-time error:
+    ///
+    /// ```compile_fail
+    /// # use myers_ed::peq::PeqBuilder;
+    /// # fn main() {
+    /// let peq = PeqBuilder::<false, false>::default().with_alphabet("ACGT").build_single::<u64>();
+    /// # }
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is longer than `8 * size_of::<T>()` bytes.
     #[inline(always)]
-    pub(crate) fn get_alphabet(&self) -> BitAlphabet
+    #[allow(private_bounds)]
+    pub fn build_single<T: Word>(self) -> SingleWordPeq<T>
     where
         (): AssertTrue<STRING_CHECK, 0>,
     {
-        let mut a = [0_u64; 4];
+        #[allow(clippy::let_unit_value)]
+        let () = <() as AssertTrue<STRING_CHECK, 0>>::CHECK;
 
-        // Safety: `AssertTrue` check guarantees string has been provided if this compiles.
-        for c in unsafe { self.string.as_ref().unwrap_unchecked() } {
-            a[(c >> 6) as usize] |= 1 << (c & 63);
-        }
+        let alphabet = self.alphabet.unwrap_or_else(|| self.get_alphabet());
 
-        BitAlphabet::new(a)
+        let string = self.string.expect("STRING_CHECK set without a string");
+
+        SingleWordPeq::from_bytes_and_alphabet(string, alphabet)
+    }
+
+    /// Build a [`MultiWordPeq`] from the provided string. If no alphabet was provided, then one
+    /// is inferred from the string. Only compiles once a string has been added with
+    /// [`PeqBuilder::with_string`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use myers_ed::peq::PeqBuilder;
+    /// # use myers_ed::scalar::multi::myers_ed_multi_scalar_with_peq;
+    /// # fn main() {
+    /// let peq = PeqBuilder::<false, false>::default()
+    ///     .with_string([b'A'; 100])
+    ///     .build_multi::<u64>();
+    ///
+    /// assert_eq!(peq.blocks(), 2);
+    /// assert_eq!(myers_ed_multi_scalar_with_peq(&peq, &[b'A'; 99]), 1);
+    /// # }
+    /// ```
+    #[inline(always)]
+    #[allow(private_bounds)]
+    pub fn build_multi<T: Word>(self) -> MultiWordPeq<T>
+    where
+        (): AssertTrue<STRING_CHECK, 0>,
+    {
+        #[allow(clippy::let_unit_value)]
+        let () = <() as AssertTrue<STRING_CHECK, 0>>::CHECK;
+
+        let alphabet = self.alphabet.unwrap_or_else(|| self.get_alphabet());
+
+        let string = self.string.expect("STRING_CHECK set without a string");
+
+        MultiWordPeq::from_bytes_and_alphabet(string, alphabet)
+    }
+
+    /// Build a [`CompactPeq`] from the provided string. If no alphabet was provided, then one
+    /// is inferred from the string. Only compiles once a string has been added with
+    /// [`PeqBuilder::with_string`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the string is longer than `8 * size_of::<T>()` bytes, or if the alphabet has
+    /// all 256 symbols.
+    #[inline(always)]
+    #[allow(private_bounds)]
+    pub fn build_compact<T: Word>(self) -> CompactPeq<T>
+    where
+        (): AssertTrue<STRING_CHECK, 0>,
+    {
+        #[allow(clippy::let_unit_value)]
+        let () = <() as AssertTrue<STRING_CHECK, 0>>::CHECK;
+
+        let alphabet = self.alphabet.unwrap_or_else(|| self.get_alphabet());
+
+        let string = self.string.expect("STRING_CHECK set without a string");
+
+        CompactPeq::from_bytes_and_alphabet(string, alphabet)
+    }
+
+    #[inline(always)]
+    #[allow(private_bounds)]
+    pub(crate) fn get_alphabet(&self) -> BitAlphabet
+    where
+        (): AssertTrue<STRING_CHECK, 0>,
+    {
+        BitAlphabet::from_bytes(
+            self.string
+                .as_ref()
+                .expect("STRING_CHECK set without a string"),
+        )
     }
 }
 
+/// Set of byte symbols stored as a 256-bit bitvector, with bit `c` set if symbol `c` is present.
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::peq::BitAlphabet;
+/// # fn main() {
+/// let read = BitAlphabet::from_bytes(b"GATTACAN");
+///
+/// assert!(!read.is_subset(BitAlphabet::DNA));
+/// assert!(read.is_subset(BitAlphabet::DNA_N));
+/// assert_eq!(read.difference(BitAlphabet::DNA).iter().collect::<Vec<_>>(), vec![b'N']);
+/// assert_eq!(BitAlphabet::PRINTABLE_ASCII.len(), 95);
+/// assert!(BitAlphabet::DNA.union(BitAlphabet::PROTEIN).is_subset(BitAlphabet::PRINTABLE_ASCII));
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
 pub struct BitAlphabet {
     bits: [u64; 4],
 }
 
 impl BitAlphabet {
-    pub(crate) fn new(bits: [u64; 4]) -> Self {
+    /// Empty alphabet.
+    pub const EMPTY: BitAlphabet = BitAlphabet::new([0; 4]);
+
+    /// Nucleotides `ACGT`.
+    pub const DNA: BitAlphabet = BitAlphabet::from_slice(b"ACGT");
+
+    /// Nucleotides `ACGT` plus the unknown base `N`.
+    pub const DNA_N: BitAlphabet = BitAlphabet::from_slice(b"ACGTN");
+
+    /// IUPAC nucleotide codes, including `U` and all ambiguity codes.
+    pub const IUPAC: BitAlphabet = BitAlphabet::from_slice(b"ACGTURYSWKMBDHVN");
+
+    /// The 20 standard amino acids.
+    pub const PROTEIN: BitAlphabet = BitAlphabet::from_slice(b"ACDEFGHIKLMNPQRSTVWY");
+
+    /// Printable ASCII, from space (`0x20`) to tilde (`0x7E`).
+    pub const PRINTABLE_ASCII: BitAlphabet =
+        BitAlphabet::new([0xFFFFFFFF00000000, 0x7FFFFFFFFFFFFFFF, 0, 0]);
+
+    pub(crate) const fn new(bits: [u64; 4]) -> Self {
         BitAlphabet { bits }
     }
 
-    pub(crate) fn into_iter(&self) -> BitAlphabetIter {
+    /// Build an alphabet containing every distinct byte in `s`.
+    pub fn from_bytes<S: AsRef<[u8]>>(s: S) -> Self {
+        Self::from_slice(s.as_ref())
+    }
+
+    const fn from_slice(s: &[u8]) -> Self {
+        let mut a = [0_u64; 4];
+
+        // `for` loops aren't allowed in `const fn`.
+        let mut i = 0;
+        while i < s.len() {
+            let c = s[i];
+            a[(c >> 6) as usize] |= 1 << (c & 63);
+            i += 1;
+        }
+
+        BitAlphabet::new(a)
+    }
+
+    /// Check if symbol `c` is in this alphabet.
+    #[inline(always)]
+    pub const fn contains(&self, c: u8) -> bool {
+        (self.bits[(c >> 6) as usize] >> (c & 63)) & 1 == 1
+    }
+
+    /// Number of symbols in this alphabet.
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        (self.bits[0].count_ones()
+            + self.bits[1].count_ones()
+            + self.bits[2].count_ones()
+            + self.bits[3].count_ones()) as usize
+    }
+
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Symbols in either `self` or `other`.
+    #[inline(always)]
+    pub const fn union(self, other: BitAlphabet) -> BitAlphabet {
+        let (a, b) = (self.bits, other.bits);
+
+        BitAlphabet::new([a[0] | b[0], a[1] | b[1], a[2] | b[2], a[3] | b[3]])
+    }
+
+    /// Symbols in both `self` and `other`.
+    #[inline(always)]
+    pub const fn intersection(self, other: BitAlphabet) -> BitAlphabet {
+        let (a, b) = (self.bits, other.bits);
+
+        BitAlphabet::new([a[0] & b[0], a[1] & b[1], a[2] & b[2], a[3] & b[3]])
+    }
+
+    /// Symbols in `self` but not in `other`.
+    #[inline(always)]
+    pub const fn difference(self, other: BitAlphabet) -> BitAlphabet {
+        let (a, b) = (self.bits, other.bits);
+
+        BitAlphabet::new([a[0] & !b[0], a[1] & !b[1], a[2] & !b[2], a[3] & !b[3]])
+    }
+
+    /// Check if every symbol in `self` is also in `other`.
+    #[inline(always)]
+    pub const fn is_subset(&self, other: BitAlphabet) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    /// Iterate over the symbols in this alphabet in ascending order.
+    #[inline(always)]
+    pub fn iter(&self) -> BitAlphabetIter {
         BitAlphabetIter::new(0, self.bits)
     }
 }
 
+impl IntoIterator for BitAlphabet {
+    type Item = u8;
+    type IntoIter = BitAlphabetIter;
+
+    fn into_iter(self) -> BitAlphabetIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for &BitAlphabet {
+    type Item = u8;
+    type IntoIter = BitAlphabetIter;
+
+    fn into_iter(self) -> BitAlphabetIter {
+        self.iter()
+    }
+}
+
 pub struct BitAlphabetIter {
     block_idx: usize,
     bits: [u64; 4],
@@ -0,0 +1,11 @@
+#![cfg(feature = "portable-simd")]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![cfg_attr(docsrs, doc(cfg(feature = "portable-simd")))]
+
+//! Backend built on `std::simd`, covering any width of `N` 64-bit lanes. Cross-lane carries and
+//! shifts are emulated with portable lane operations, so no target features are required.
+
+pub mod plumbing;
+pub mod single;
+
+mod word;
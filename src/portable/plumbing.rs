@@ -0,0 +1,156 @@
+//! Portable "helper intrinsics" on `Simd<u64, N>` for supporting the Myers algorithm
+//! implementation. These mirror `avx512::plumbing` for any lane count.
+
+use std::simd::prelude::*;
+
+/// Add two `Simd<u64, N>` as if they were both one-lane `64 * N`-bit integers. Any lane of the
+/// sum of `a` and `b` that overflows must carry a bit into the next lane. As in
+/// `_mm256_add_si256_custom`, every carry chain is resolved in constant time by adding the masks
+/// of lanes that generate and propagate a carry in a general purpose register.
+///
+/// # Examples
+///
+/// ```
+/// # #![feature(portable_simd)]
+/// # use std::simd::Simd;
+/// # use myers_ed::portable::plumbing::simd_add_custom;
+/// # fn main() {
+/// // Note that memory-layout is little-endian. The carry out of lane 0 ripples through lane 1,
+/// // which sums to all ones, into lane 2.
+/// let a = Simd::from_array([u64::MAX, u64::MAX, 0, 0]);
+/// let b = Simd::from_array([1, 0, 0, 0]);
+///
+/// assert_eq!(simd_add_custom(a, b), Simd::from_array([0, 0, 1, 0]));
+///
+/// // Agrees with the hand-written AVX-512 intrinsic.
+/// # #[cfg(feature = "avx512")]
+/// # {
+/// # use core::arch::x86_64::*;
+/// # use myers_ed::avx512::plumbing::_mm512_add_si512_custom;
+/// let a = Simd::from_array([u64::MAX, u64::MAX, 3, 0, u64::MAX, 0, 0, 1]);
+/// let b = Simd::from_array([1, 0, u64::MAX, 0, 1, u64::MAX, 0, 2]);
+///
+/// let s1: Simd<u64, 8> = _mm512_add_si512_custom::<1>(a.into(), b.into()).into();
+/// let s2 = simd_add_custom(a, b);
+///
+/// assert_eq!(s1, s2);
+/// # }
+/// # }
+/// ```
+#[inline(always)]
+pub fn simd_add_custom<const N: usize>(a: Simd<u64, N>, b: Simd<u64, N>) -> Simd<u64, N> {
+    // Add a and b together as 64-bit lanes without carry between.
+    let s = a + b;
+
+    // Mask of lanes generating a carry. If s < a, then we overflowed.
+    let g = s.simd_lt(a).to_bitmask();
+
+    // Mask of lanes propagating a carry. If s = 0xFFFFFFFFFFFFFFFF, an incoming carry overflows
+    // this lane too.
+    let p = s.simd_eq(Simd::splat(u64::MAX)).to_bitmask();
+
+    // Mask of lanes receiving a carry. Adding the shifted generate bits into the propagate bits
+    // ripples each carry through its run of propagating lanes, and the XOR picks out every lane
+    // whose bit was touched by a carry. The carry out of the last lane is discarded.
+    let c = (g << 1).wrapping_add(p) ^ p;
+
+    // Add carry bits into s.
+    s + Mask::<i64, N>::from_bitmask(c).select(Simd::splat(1), Simd::splat(0))
+}
+
+/// Left shift all bits within a `Simd<u64, N>` by `IMM8` bits, while shifting in zeros. `IMM8`
+/// must be less than `64`.
+///
+/// # Examples
+///
+/// ```
+/// # #![feature(portable_simd)]
+/// # use std::simd::Simd;
+/// # use myers_ed::portable::plumbing::simd_slli_custom;
+/// # fn main() {
+/// let a = Simd::from_array([1 << 63, 0, 0, 1]);
+///
+/// assert_eq!(simd_slli_custom::<2, 4>(a), Simd::from_array([0, 2, 0, 4]));
+///
+/// // Agrees with the hand-written AVX-512 intrinsic.
+/// # #[cfg(feature = "avx512")]
+/// # {
+/// # use core::arch::x86_64::*;
+/// # use myers_ed::avx512::plumbing::_mm512_slli_si512_custom;
+/// let a = Simd::from_array([u64::MAX, 1 << 63, 7, 0, 1 << 62, 0, 0, 1]);
+///
+/// let s1: Simd<u64, 8> = _mm512_slli_si512_custom::<3>(a.into()).into();
+/// let s2 = simd_slli_custom::<3, 8>(a);
+///
+/// assert_eq!(s1, s2);
+/// # }
+/// # }
+/// ```
+#[inline(always)]
+pub fn simd_slli_custom<const IMM8: u32, const N: usize>(a: Simd<u64, N>) -> Simd<u64, N> {
+    // Folded away at compile time, as `IMM8` is a `const` generic.
+    assert!(IMM8 < 64, "IMM8 must be less than 64");
+
+    // A zero shift would need a right shift by the full lane width below.
+    if IMM8 == 0 {
+        return a;
+    }
+
+    // Bits shifted out of the top of each lane, moved up into the bottom of the next lane. The
+    // top lane's bits wrap around into lane 0, which we then clear.
+    let mut o = (a >> Simd::splat(64 - IMM8 as u64)).rotate_elements_right::<1>();
+    o.as_mut_array()[0] = 0;
+
+    (a << Simd::splat(IMM8 as u64)) | o
+}
+
+/// Compute a bitmask where every bit below bit `i` is set to 1, and the rest are zeroed.
+///
+/// # Examples
+///
+/// ```
+/// # #![feature(portable_simd)]
+/// # use std::simd::Simd;
+/// # use myers_ed::portable::plumbing::simd_mask_upto_custom;
+/// # fn main() {
+/// // Safety: 72 <= 256.
+/// let m = unsafe { simd_mask_upto_custom::<4>(72) };
+///
+/// assert_eq!(m, Simd::from_array([u64::MAX, 255, 0, 0]));
+/// # }
+/// ```
+///
+/// # Safety
+///
+/// Caller must guarantee that `i <= 64 * N`.
+#[inline(always)]
+pub unsafe fn simd_mask_upto_custom<const N: usize>(i: usize) -> Simd<u64, N> {
+    let mut m = [0_u64; N];
+
+    for (l, m) in m.iter_mut().enumerate() {
+        // Number of real bits in this lane. A full 64-bit mask would overflow the shift.
+        let bits = i.saturating_sub(64 * l).min(64);
+        *m = 1_u64.checked_shl(bits as u32).unwrap_or(0).wrapping_sub(1);
+    }
+
+    Simd::from_array(m)
+}
+
+/// Count the set bits in all lanes of a `Simd<u64, N>`.
+///
+/// # Examples
+///
+/// ```
+/// # #![feature(portable_simd)]
+/// # use std::simd::Simd;
+/// # use myers_ed::portable::plumbing::simd_popcnt_custom;
+/// # fn main() {
+/// let a = Simd::from_array([1, 0, 0, 15]);
+///
+/// assert_eq!(5, simd_popcnt_custom(a));
+/// # }
+/// ```
+#[inline(always)]
+pub fn simd_popcnt_custom<const N: usize>(a: Simd<u64, N>) -> u64 {
+    a.count_ones().reduce_sum()
+}
@@ -0,0 +1,46 @@
+use std::simd::Simd;
+
+use anyhow::{Result, anyhow};
+
+use crate::generic::single::myers_ed_single_with_peq;
+use crate::peq::{Peq, SingleWordPeq};
+
+/// Perform Myers algorithm to find the edit distance between `a` and `b`. Uses portable SIMD with
+/// `64 * N`-bit words. Input bytes `a` must be `<= 64 * N` bytes. Input bytes `b` can be any length.
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::portable::single::myers_ed_single_portable;
+/// # fn main() {
+/// let a = [b'A'; 200];
+/// let mut b = [b'A'; 200];
+/// b[150] = b'C';
+///
+/// assert_eq!(myers_ed_single_portable::<4>(&a, &b), 1);
+/// # }
+/// ```
+pub fn myers_ed_single_portable<const N: usize>(a: &[u8], b: &[u8]) -> usize {
+    assert!(a.len() <= 64 * N, "Input must be <= {} bytes", 64 * N);
+
+    let peq = SingleWordPeq::<Simd<u64, N>>::from_bytes(a);
+
+    myers_ed_single_portable_with_peq(&peq, b)
+}
+
+pub fn try_myers_ed_single_portable<const N: usize>(a: &[u8], b: &[u8]) -> Result<usize> {
+    if a.len() > 64 * N {
+        return Err(anyhow!("Input must be <= {} bytes", 64 * N));
+    }
+
+    let peq = SingleWordPeq::<Simd<u64, N>>::from_bytes(a);
+
+    Ok(myers_ed_single_portable_with_peq(&peq, b))
+}
+
+pub fn myers_ed_single_portable_with_peq<const N: usize, P: Peq<Simd<u64, N>>>(
+    peq: &P,
+    b: &[u8],
+) -> usize {
+    myers_ed_single_with_peq(peq, b)
+}
@@ -0,0 +1,121 @@
+//! [`Word`] implementation for portable `Simd<u64, N>` vectors.
+
+use std::simd::prelude::*;
+
+use crate::portable::plumbing::*;
+use crate::word::Word;
+
+impl<const N: usize> Word for Simd<u64, N> {
+    const ZERO: Simd<u64, N> = Simd::from_array([0; N]);
+
+    const ONE: Simd<u64, N> = {
+        let mut a = [0; N];
+        if N > 0 {
+            a[0] = 1;
+        }
+
+        Simd::from_array(a)
+    };
+
+    const ONES: Simd<u64, N> = Simd::from_array([u64::MAX; N]);
+
+    #[inline(always)]
+    unsafe fn bit_at_unchecked(i: usize) -> Simd<u64, N> {
+        let mut a = [0_u64; N];
+
+        // Set bit inside selected lane.
+        *a.get_unchecked_mut(i >> 6) = 1_u64 << (i & 63);
+
+        Simd::from_array(a)
+    }
+
+    #[inline(always)]
+    fn bit_or(self, rhs: Simd<u64, N>) -> Simd<u64, N> {
+        self | rhs
+    }
+
+    #[inline(always)]
+    fn bit_and(self, rhs: Simd<u64, N>) -> Simd<u64, N> {
+        self & rhs
+    }
+
+    #[inline(always)]
+    fn bit_xor(self, rhs: Simd<u64, N>) -> Simd<u64, N> {
+        self ^ rhs
+    }
+
+    #[inline(always)]
+    fn bit_not(self) -> Simd<u64, N> {
+        !self
+    }
+
+    #[inline(always)]
+    fn wide_add(self, rhs: Simd<u64, N>) -> Simd<u64, N> {
+        simd_add_custom(self, rhs)
+    }
+
+    #[inline(always)]
+    fn shl_one(self, carry: bool) -> Simd<u64, N> {
+        let mut s = simd_slli_custom::<1, N>(self);
+        s.as_mut_array()[0] |= carry as u64;
+
+        s
+    }
+
+    #[inline(always)]
+    fn shr_one(self) -> Simd<u64, N> {
+        // Bits shifted out of the bottom of each lane, moved down into the top of the previous
+        // lane. Lane 0's bit wraps around into the top lane, which we then clear.
+        let mut u = (self << Simd::splat(63)).rotate_elements_left::<1>();
+        if let Some(x) = u.as_mut_array().last_mut() {
+            *x = 0;
+        }
+
+        (self >> Simd::splat(1)) | u
+    }
+
+    #[inline(always)]
+    fn msb(self) -> bool {
+        self.as_array().last().is_some_and(|&x| x >> 63 == 1)
+    }
+
+    #[inline(always)]
+    unsafe fn popcnt_upto_unchecked(self, n: usize) -> usize {
+        // Safety: caller guarantees that `n <= 64 * N`.
+        let m = simd_mask_upto_custom::<N>(n);
+
+        simd_popcnt_custom(self & m) as usize
+    }
+
+    /// `N` 64x8-bit lanes, zero-padded, and a mask of the real bytes in each lane.
+    type Spread = ([Simd<u8, 64>; N], Simd<u64, N>);
+
+    #[inline(always)]
+    unsafe fn spread_bytes_unchecked(s: &[u8]) -> ([Simd<u8, 64>; N], Simd<u64, N>) {
+        let mut lanes = [Simd::splat(0); N];
+
+        // Copy each chunk into a zero-padded lane.
+        for (l, chunk) in lanes.iter_mut().zip(s.chunks(64)) {
+            *l = Simd::load_or_default(chunk);
+        }
+
+        // Padding bytes compare equal to symbol `0`, so remember which bits are real.
+        (lanes, simd_mask_upto_custom::<N>(s.len()))
+    }
+
+    #[inline(always)]
+    fn eq_mask(spread: &([Simd<u8, 64>; N], Simd<u64, N>), c: u8) -> Simd<u64, N> {
+        let (lanes, m) = spread;
+
+        // Broadcast symbol across all 64 lanes.
+        let c = Simd::splat(c);
+
+        // Each 64-byte lane compares into exactly one 64-bit lane of the result.
+        let mut eq = [0_u64; N];
+        for (eq, l) in eq.iter_mut().zip(lanes.iter()) {
+            *eq = l.simd_eq(c).to_bitmask();
+        }
+
+        Simd::from_array(eq) & *m
+    }
+}
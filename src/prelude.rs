@@ -1,10 +1,67 @@
 //! Prelude for common functions and types available in this crate.
 
+#[cfg(feature = "avx2")]
+pub use crate::avx2::single::{
+    myers_ed_single_avx2, myers_ed_single_avx2_with_peq, try_myers_ed_single_avx2,
+};
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
+pub use crate::avx512::banded::{myers_ed_banded_avx512, try_myers_ed_banded_avx512};
+#[cfg(feature = "avx512-vbmi")]
+pub use crate::avx512::batch::myers_ed_batch_avx512_with_alphabet;
 #[cfg(feature = "avx512")]
+pub use crate::avx512::batch::{
+    BatchStreamAvx512, myers_ed_batch_avx512, myers_ed_batch_avx512_with_peq,
+    myers_ed_batch_stream_avx512, myers_ed_batch16_avx512, myers_ed_batch16_avx512_with_peq,
+    try_myers_ed_batch_avx512, try_myers_ed_batch16_avx512,
+};
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
+pub use crate::avx512::multi::{
+    myers_ed_multi_avx512, myers_ed_multi_avx512_bounded_with_peq, myers_ed_multi_avx512_with_peq,
+    try_myers_ed_multi_avx512,
+};
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
+pub use crate::avx512::search::myers_ed_search_avx512_with_peq;
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
+pub use crate::avx512::single::{
+    myers_ed_single_avx512, myers_ed_single_avx512_align, myers_ed_single_avx512_align_with_peq,
+    myers_ed_single_avx512_bounded_with_peq, myers_ed_single_avx512_with_mode,
+    myers_ed_single_avx512_with_peq, myers_ed_single_avx512_with_peq_and_mode,
+    try_myers_ed_single_avx512, try_myers_ed_single_avx512_align,
+    try_myers_ed_single_avx512_with_mode,
+};
+#[cfg(feature = "avx512-epi32")]
 pub use crate::avx512::single::{
-    myers_ed_single_avx512, myers_ed_single_avx512_with_peq, try_myers_ed_single_avx512,
+    myers_ed_single_avx512_epi32, myers_ed_single_avx512_epi32_with_peq,
+    try_myers_ed_single_avx512_epi32,
 };
-pub use crate::peq::SingleWordPeq;
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
+pub use crate::avx512::u512::U512;
+pub use crate::dispatch::{Backend, DispatchPeq, myers_ed, myers_ed_with_peq, try_myers_ed};
+pub use crate::generic::align::Alignment;
+pub use crate::mode::Mode;
+pub use crate::peq::{BitAlphabet, CompactPeq, MultiWordPeq, Peq, SingleWordPeq};
+#[cfg(feature = "portable-simd")]
+pub use crate::portable::single::{
+    myers_ed_single_portable, myers_ed_single_portable_with_peq, try_myers_ed_single_portable,
+};
+pub use crate::scalar::banded::{myers_ed_banded_scalar, try_myers_ed_banded_scalar};
+pub use crate::scalar::multi::{
+    myers_ed_multi_scalar, myers_ed_multi_scalar_bounded_with_peq, myers_ed_multi_scalar_with_peq,
+    try_myers_ed_multi_scalar,
+};
+pub use crate::scalar::search::myers_ed_search_scalar_with_peq;
 pub use crate::scalar::single::{
-    myers_ed_single_scalar, myers_ed_single_scalar_with_peq, try_myers_ed_single_scalar,
+    myers_ed_single_scalar, myers_ed_single_scalar_align, myers_ed_single_scalar_align_with_peq,
+    myers_ed_single_scalar_bounded_with_peq, myers_ed_single_scalar_with_mode,
+    myers_ed_single_scalar_with_peq, myers_ed_single_scalar_with_peq_and_mode,
+    try_myers_ed_single_scalar, try_myers_ed_single_scalar_align,
+    try_myers_ed_single_scalar_with_mode,
+};
+pub use crate::scalar::wide::{
+    myers_ed_single_u128, myers_ed_single_u128_with_peq, myers_ed_single_wide,
+    myers_ed_single_wide_with_peq, try_myers_ed_single_u128, try_myers_ed_single_wide,
+};
+#[cfg(feature = "sse")]
+pub use crate::sse::single::{
+    myers_ed_single_sse, myers_ed_single_sse_with_peq, try_myers_ed_single_sse,
 };
@@ -0,0 +1,33 @@
+use anyhow::{Result, anyhow};
+
+use crate::generic::banded::myers_ed_banded;
+
+/// Perform banded Myers algorithm to find the edit distance between `a` and `b` if it is at most
+/// `k`, and `None` otherwise. Uses a band of `2k + 1` diagonals in one 64-bit word, so `k` must
+/// be `<= 31`. Input bytes `a` and `b` can both be any length, and the cost is linear in them.
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::scalar::banded::myers_ed_banded_scalar;
+/// # fn main() {
+/// let a = b"ACGT".repeat(1000);
+/// let mut b = a.clone();
+/// b.insert(2000, b'C');
+///
+/// assert_eq!(myers_ed_banded_scalar(&a, &b, 2), Some(1));
+/// # }
+/// ```
+pub fn myers_ed_banded_scalar(a: &[u8], b: &[u8], k: usize) -> Option<usize> {
+    assert!(k <= 31, "k must be <= 31");
+
+    myers_ed_banded::<u64>(a, b, k)
+}
+
+pub fn try_myers_ed_banded_scalar(a: &[u8], b: &[u8], k: usize) -> Result<Option<usize>> {
+    if k > 31 {
+        return Err(anyhow!("k must be <= 31"));
+    }
+
+    Ok(myers_ed_banded::<u64>(a, b, k))
+}
@@ -1 +1,5 @@
+pub mod banded;
+pub mod multi;
+pub mod search;
 pub mod single;
+pub mod wide;
@@ -0,0 +1,45 @@
+use anyhow::Result;
+
+use crate::generic::multi::{myers_ed_multi_bounded_with_peq, myers_ed_multi_with_peq};
+use crate::peq::MultiWordPeq;
+
+/// Perform blocked Myers algorithm to find the edit distance between `a` and `b`. Uses chains of
+/// 64-bit words, so input bytes `a` and `b` can both be any length.
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::scalar::multi::myers_ed_multi_scalar;
+/// # fn main() {
+/// let a = [b'A'; 100];
+/// let mut b = [b'A'; 101];
+/// b[70] = b'C';
+///
+/// assert_eq!(myers_ed_multi_scalar(&a, &b), 1);
+/// # }
+/// ```
+pub fn myers_ed_multi_scalar(a: &[u8], b: &[u8]) -> usize {
+    let peq = MultiWordPeq::from_bytes(a);
+
+    myers_ed_multi_scalar_with_peq(&peq, b)
+}
+
+pub fn try_myers_ed_multi_scalar(a: &[u8], b: &[u8]) -> Result<usize> {
+    let peq = MultiWordPeq::try_from_bytes(a)?;
+
+    Ok(myers_ed_multi_scalar_with_peq(&peq, b))
+}
+
+pub fn myers_ed_multi_scalar_with_peq(peq: &MultiWordPeq<u64>, b: &[u8]) -> usize {
+    myers_ed_multi_with_peq(peq, b)
+}
+
+/// Like `myers_ed_multi_scalar_with_peq`, but returns `None` as soon as the edit distance is known to
+/// be greater than `k`. See [`myers_ed_multi_bounded_with_peq`].
+pub fn myers_ed_multi_scalar_bounded_with_peq(
+    peq: &MultiWordPeq<u64>,
+    b: &[u8],
+    k: usize,
+) -> Option<usize> {
+    myers_ed_multi_bounded_with_peq(peq, b, k)
+}
@@ -0,0 +1,26 @@
+use crate::generic::search::{Search, myers_ed_search_with_peq};
+use crate::peq::Peq;
+
+/// Perform Myers algorithm in search mode to find every end position in `text` of a substring
+/// within edit distance `k` of the string encoded in `peq`. Uses 64-bit words, so the pattern must
+/// be `<= 64` bytes. Yields `(end_pos, distance)`. See [`myers_ed_search_with_peq`].
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::scalar::search::myers_ed_search_scalar_with_peq;
+/// # use myers_ed::peq::SingleWordPeq;
+/// # fn main() {
+/// let peq = SingleWordPeq::from_bytes(b"GATTACA");
+/// let best = myers_ed_search_scalar_with_peq(&peq, b"CCGATACACC", 2).min_by_key(|&(_, d)| d);
+///
+/// assert_eq!(best, Some((8, 1)));
+/// # }
+/// ```
+pub fn myers_ed_search_scalar_with_peq<'a, P: Peq<u64>>(
+    peq: &'a P,
+    text: &'a [u8],
+    k: usize,
+) -> Search<'a, u64, P> {
+    myers_ed_search_with_peq(peq, text, k)
+}
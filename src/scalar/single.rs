@@ -1,6 +1,11 @@
 use anyhow::{Result, anyhow};
 
-use crate::peq::SingleWordPeq;
+use crate::generic::align::{Alignment, myers_ed_single_align_with_peq};
+use crate::generic::single::{
+    myers_ed_single_bounded_with_peq, myers_ed_single_with_peq, myers_ed_single_with_peq_and_mode,
+};
+use crate::mode::Mode;
+use crate::peq::{Peq, SingleWordPeq};
 
 /// Perform Myers algorithm to find the edit distance between `a` and `b`. Uses 64-bit words.
 /// Input bytes `a` must be `<= 64` bytes. Input bytes `b` can be any length.
@@ -33,44 +38,104 @@ pub fn try_myers_ed_single_scalar(a: &[u8], b: &[u8]) -> Result<usize> {
     Ok(myers_ed_single_scalar_with_peq(&peq, b))
 }
 
-pub fn myers_ed_single_scalar_with_peq(peq: &SingleWordPeq<u64>, b: &[u8]) -> usize {
-    // Vertical positive delta bit-vector.
-    let mut vp = u64::MAX;
+pub fn myers_ed_single_scalar_with_peq<P: Peq<u64>>(peq: &P, b: &[u8]) -> usize {
+    myers_ed_single_with_peq(peq, b)
+}
+
+/// Like `myers_ed_single_scalar_with_peq`, but returns `None` as soon as the edit distance is known to
+/// be greater than `k`. See [`myers_ed_single_bounded_with_peq`].
+pub fn myers_ed_single_scalar_bounded_with_peq<P: Peq<u64>>(
+    peq: &P,
+    b: &[u8],
+    k: usize,
+) -> Option<usize> {
+    myers_ed_single_bounded_with_peq(peq, b, k)
+}
 
-    // Vertical negative delta bit-vector.
-    let mut vn = 0_u64;
+/// Perform Myers algorithm to align `a` to `b` as `mode` says, returning `(distance, end)` where
+/// `b[..end]` is the part of `b` that `a` was aligned to. Uses 64-bit words.
+/// Input bytes `a` must be `<= 64` bytes. Input bytes `b` can be any length. See
+/// [`myers_ed_single_with_peq_and_mode`].
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::scalar::single::myers_ed_single_scalar_with_mode;
+/// # use myers_ed::mode::Mode;
+/// # fn main() {
+/// let (d, end) = myers_ed_single_scalar_with_mode(b"ACCC", b"ACGCTTTT", Mode::Prefix);
+///
+/// assert_eq!((d, end), (1, 4));
+/// # }
+/// ```
+pub fn myers_ed_single_scalar_with_mode(a: &[u8], b: &[u8], mode: Mode) -> (usize, usize) {
+    assert!(a.len() <= 64, "Input must be <= 64 bytes");
 
-    // Update loop.
-    for &x in b {
-        // Get the equality mask for the current character.
-        //
-        // Infallible: `x as usize` \in [0, 255] and `peq.peq.len() == 256`.
-        let eq = peq[x as usize];
+    let peq = SingleWordPeq::from_bytes(a);
 
-        // Calculate diagonal zero delta bit-vector.
-        let d0 = (((eq & vp).saturating_add(vp)) ^ vp) | eq;
+    myers_ed_single_scalar_with_peq_and_mode(&peq, b, mode)
+}
 
-        // Calculate horizontal delta bit-vectors.
-        let mut hp = vn | !(vp | d0);
-        let hn = vp & d0;
+pub fn try_myers_ed_single_scalar_with_mode(
+    a: &[u8],
+    b: &[u8],
+    mode: Mode,
+) -> Result<(usize, usize)> {
+    if a.len() > 64 {
+        return Err(anyhow!("Input must be <= 64 bytes"));
+    }
 
-        // Calculate intermediate mask for next column's vertical delta bits.
-        let xh = eq | vn;
+    let peq = SingleWordPeq::from_bytes(a);
 
-        // Move one column right in DP matrix.
-        hp = (hp << 1_u64) | 1_u64;
+    Ok(myers_ed_single_scalar_with_peq_and_mode(&peq, b, mode))
+}
 
-        // Update vertical delta bit-vectors.
-        vp = (hn << 1_u64) | !(xh | hp);
-        vn = hp & xh;
+pub fn myers_ed_single_scalar_with_peq_and_mode<P: Peq<u64>>(
+    peq: &P,
+    b: &[u8],
+    mode: Mode,
+) -> (usize, usize) {
+    myers_ed_single_with_peq_and_mode(peq, b, mode)
+}
+
+/// Perform Myers algorithm to find an optimal alignment of `a` to `b` as `mode` says, with its
+/// CIGAR string. Uses 64-bit words. Input bytes `a` must be `<= 64` bytes. Input bytes `b` can
+/// be any length. See [`myers_ed_single_align_with_peq`].
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::scalar::single::myers_ed_single_scalar_align;
+/// # use myers_ed::mode::Mode;
+/// # fn main() {
+/// let aln = myers_ed_single_scalar_align(b"GATTACA", b"GATACAGG", Mode::Prefix);
+///
+/// assert_eq!((aln.start, aln.end, aln.distance), (0, 6, 1));
+/// assert_eq!(aln.cigar, "2=1I4=");
+/// # }
+/// ```
+pub fn myers_ed_single_scalar_align(a: &[u8], b: &[u8], mode: Mode) -> Alignment {
+    assert!(a.len() <= 64, "Input must be <= 64 bytes");
+
+    let peq = SingleWordPeq::from_bytes(a);
+
+    myers_ed_single_scalar_align_with_peq(&peq, b, mode)
+}
+
+pub fn try_myers_ed_single_scalar_align(a: &[u8], b: &[u8], mode: Mode) -> Result<Alignment> {
+    if a.len() > 64 {
+        return Err(anyhow!("Input must be <= 64 bytes"));
     }
 
-    // Compute mask to get only real bits.
-    let m = (1_u64 << peq.len()).wrapping_sub(1);
+    let peq = SingleWordPeq::from_bytes(a);
 
-    // Compute final edit distance.
-    let vp_popcnt = (vp & m).count_ones();
-    let vn_popcnt = (vn & m).count_ones();
+    Ok(myers_ed_single_scalar_align_with_peq(&peq, b, mode))
+}
 
-    b.len() + vp_popcnt as usize - vn_popcnt as usize
+pub fn myers_ed_single_scalar_align_with_peq<P: Peq<u64>>(
+    peq: &P,
+    b: &[u8],
+    mode: Mode,
+) -> Alignment {
+    myers_ed_single_align_with_peq(peq, b, mode)
 }
@@ -0,0 +1,82 @@
+//! Portable single-word kernels for patterns too long for a `u64`, using `u128` and `[u64; N]`
+//! words instead of SIMD.
+
+use anyhow::{Result, anyhow};
+
+use crate::generic::single::myers_ed_single_with_peq;
+use crate::peq::{Peq, SingleWordPeq};
+
+/// Perform Myers algorithm to find the edit distance between `a` and `b`. Uses 128-bit words.
+/// Input bytes `a` must be `<= 128` bytes. Input bytes `b` can be any length.
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::scalar::wide::myers_ed_single_u128;
+/// # fn main() {
+/// let a = [b'A'; 100];
+/// let mut b = [b'A'; 100];
+/// b[80] = b'C';
+///
+/// assert_eq!(myers_ed_single_u128(&a, &b), 1);
+/// # }
+/// ```
+pub fn myers_ed_single_u128(a: &[u8], b: &[u8]) -> usize {
+    assert!(a.len() <= 128, "Input must be <= 128 bytes");
+
+    let peq = SingleWordPeq::from_bytes(a);
+
+    myers_ed_single_u128_with_peq(&peq, b)
+}
+
+pub fn try_myers_ed_single_u128(a: &[u8], b: &[u8]) -> Result<usize> {
+    if a.len() > 128 {
+        return Err(anyhow!("Input must be <= 128 bytes"));
+    }
+
+    let peq = SingleWordPeq::from_bytes(a);
+
+    Ok(myers_ed_single_u128_with_peq(&peq, b))
+}
+
+pub fn myers_ed_single_u128_with_peq<P: Peq<u128>>(peq: &P, b: &[u8]) -> usize {
+    myers_ed_single_with_peq(peq, b)
+}
+
+/// Perform Myers algorithm to find the edit distance between `a` and `b`. Uses `64 * N`-bit words
+/// made of `N` `u64` limbs. Input bytes `a` must be `<= 64 * N` bytes. Input bytes `b` can be any
+/// length.
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::scalar::wide::myers_ed_single_wide;
+/// # fn main() {
+/// let a = [b'A'; 200];
+/// let mut b = [b'A'; 200];
+/// b[150] = b'C';
+///
+/// assert_eq!(myers_ed_single_wide::<4>(&a, &b), 1);
+/// # }
+/// ```
+pub fn myers_ed_single_wide<const N: usize>(a: &[u8], b: &[u8]) -> usize {
+    assert!(a.len() <= 64 * N, "Input must be <= {} bytes", 64 * N);
+
+    let peq = SingleWordPeq::<[u64; N]>::from_bytes(a);
+
+    myers_ed_single_wide_with_peq(&peq, b)
+}
+
+pub fn try_myers_ed_single_wide<const N: usize>(a: &[u8], b: &[u8]) -> Result<usize> {
+    if a.len() > 64 * N {
+        return Err(anyhow!("Input must be <= {} bytes", 64 * N));
+    }
+
+    let peq = SingleWordPeq::<[u64; N]>::from_bytes(a);
+
+    Ok(myers_ed_single_wide_with_peq(&peq, b))
+}
+
+pub fn myers_ed_single_wide_with_peq<const N: usize, P: Peq<[u64; N]>>(peq: &P, b: &[u8]) -> usize {
+    myers_ed_single_with_peq(peq, b)
+}
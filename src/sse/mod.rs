@@ -0,0 +1,15 @@
+#![cfg(feature = "sse")]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+#![cfg_attr(docsrs, doc(cfg(feature = "sse")))]
+
+use crate::assert_target_features;
+
+pub mod plumbing;
+pub mod single;
+
+mod word;
+
+// Safety: the safe functions in this module using SSE intrinsics are safe
+// because we guarantee here that sse2 is available when the sse crate
+// feature compiles. Only SSE2 is used, so this holds on every x86_64 CPU.
+assert_target_features! { "sse", "sse2" }
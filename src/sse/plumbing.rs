@@ -0,0 +1,174 @@
+//! Custom SSE "helper intrinsics" for supporting the Myers algorithm implementation. Only SSE2
+//! instructions are used, so these run on any x86_64 CPU.
+
+use core::arch::x86_64::*;
+
+/// Add two `__m128i` as if they were both one-lane 128-bit integers. The operation works with
+/// 64-bit lanes, so if lane 0 of the sum of `a` and `b` overflows, we carry a bit into lane 1.
+/// With only two lanes, one carry round is always enough: the carry out of lane 1 is discarded.
+///
+/// # Examples
+///
+/// ```
+/// # use core::arch::x86_64::*;
+/// # use myers_ed::sse::plumbing::_mm_add_si128_custom;
+/// # fn main() {
+/// // Note that memory-layout is little-endian, so the carry out of lane 0 goes into lane 1.
+/// let a: __m128i = unsafe { core::mem::transmute::<[i64; 2], _>([-1, 1]) };
+/// let b: __m128i = unsafe { core::mem::transmute::<[i64; 2], _>([1, 0]) };
+///
+/// let s1: __m128i = unsafe { core::mem::transmute::<[i64; 2], _>([0, 2]) };
+/// let s2: __m128i = _mm_add_si128_custom(a, b);
+/// # // __m128i doesn't implement PartialEq, so quietly transmute back to arrays.
+/// # let s1: [i64; 2] = unsafe { core::mem::transmute::<__m128i, _>(s1)};
+/// # let s2: [i64; 2] = unsafe { core::mem::transmute::<__m128i, _>(s2)};
+///
+/// assert_eq!(s1, s2);
+/// # }
+/// ```
+#[inline(always)]
+pub fn _mm_add_si128_custom(a: __m128i, b: __m128i) -> __m128i {
+    // Safety
+    //
+    // The `sse2` `target_feature` must be available.
+    #[inline(always)]
+    unsafe fn __inner_mm_add_si128_custom(a: __m128i, b: __m128i) -> __m128i {
+        // Add a and b together as 64-bit lanes without carry between.
+        let s = _mm_add_epi64(a, b);
+
+        // SSE2 has no 64-bit compares, so find the carry out of each lane from the MSBs. A lane
+        // carries if both MSBs were set, or if either was set and the sum's MSB was cleared.
+        // This is (a & b) | ((a | b) & !s), shifted down to the LSB.
+        let c = _mm_or_si128(_mm_and_si128(a, b), _mm_andnot_si128(s, _mm_or_si128(a, b)));
+        let c = _mm_srli_epi64::<63>(c);
+
+        // Move lane 0's carry into lane 1, dropping lane 1's carry.
+        let cb = _mm_slli_si128::<8>(c);
+
+        _mm_add_epi64(s, cb)
+    }
+
+    // Safety: We guarantee that the sse2 target_feature is available when the sse crate feature compiles.
+    unsafe { __inner_mm_add_si128_custom(a, b) }
+}
+
+// Trick to evaluate a `const` context that doesn't cause a well-formedness check cycle.
+// This way the `const` context can be evaluated separately from a well-formedness proof.
+trait ConstExpr<const X: i32> {}
+impl<const X: i32> ConstExpr<X> for () {}
+
+/// Left shift all bits within a 128-bit `__m128i` type by `IMM8` bits, while shifting in zeros.
+/// `IMM8` is a `const` generic immediate and must be between `0` and `64`. `IMM8` is passed as an
+/// `i32` to match Rust's SSE intrinsic ABI. See `_mm512_slli_si512_custom` for the reasoning
+/// behind the type-level trickery.
+///
+/// # Examples
+///
+/// ```
+/// # use core::arch::x86_64::*;
+/// # use myers_ed::sse::plumbing::_mm_slli_si128_custom;
+/// # fn main() {
+/// // Bits shifted out of the top of lane 0 are shifted into the bottom of lane 1.
+/// let a: __m128i = unsafe { core::mem::transmute::<[i64; 2], _>([i64::MIN, 1]) };
+/// let b: __m128i = unsafe { core::mem::transmute::<[i64; 2], _>([0, 12]) };
+///
+/// let s: __m128i = _mm_slli_si128_custom::<3>(a);
+/// # // __m128i doesn't implement PartialEq, so quietly transmute back to arrays.
+/// # let b: [i64; 2] = unsafe { core::mem::transmute::<__m128i, _>(b)};
+/// # let s: [i64; 2] = unsafe { core::mem::transmute::<__m128i, _>(s)};
+///
+/// assert_eq!(b, s);
+/// # }
+/// ```
+#[inline(always)]
+#[allow(private_bounds)]
+pub fn _mm_slli_si128_custom<const IMM8: i32>(a: __m128i) -> __m128i
+where
+    // Constrain IMM8 to be <= 64.
+    (): ConstExpr<{ 64 - IMM8 }>,
+{
+    struct Complementor;
+
+    trait ComplementShift<const N: i32, const Q: i32> {
+        // Perform the actual left shift calculation now that we are in a scope with both `N`
+        // and `Q` as available `const` generics.
+        //
+        // Safety
+        //
+        // `N` must be between 0 and 64. The `sse2` `target_feature` must be available.
+        #[inline(always)]
+        unsafe fn __inner_mm_slli_si128_custom(a: __m128i) -> __m128i {
+            // Left shift lanes in a by N without carrying between lanes.
+            let s = _mm_slli_epi64::<N>(a);
+
+            // Shift lane 0 up into lane 1 by bytes, then right shift by Q = 64 - N to get all the
+            // bits that overflowed out of lane 0.
+            let m = _mm_srli_epi64::<Q>(_mm_slli_si128::<8>(a));
+
+            // Fill shifted in zero bits with overflowed bits from previous lane.
+            _mm_or_si128(s, m)
+        }
+    }
+
+    impl<const N: i32, const Q: i32> ComplementShift<N, Q> for Complementor {}
+
+    // Safety: outer function signature guarantees that `N <= 64`. We also guarantee that
+    // the sse2 target_feature is available when the sse crate feature compiles.
+    unsafe {
+        <Complementor as ComplementShift<IMM8, { 64 - IMM8 }>>::__inner_mm_slli_si128_custom(a)
+    }
+}
+
+/// Return a bitmask of the lowest `i` bits set. I.e. `dst[127:i] = 0` and `dst[(i-1):0] = 1`.
+///
+/// # Examples
+///
+/// ```
+/// # use core::arch::x86_64::*;
+/// # use myers_ed::sse::plumbing::_mm_mask_upto_si128_custom;
+/// # fn main() {
+/// let m1: __m128i = unsafe { core::mem::transmute::<[i64; 2], _>([-1, 255]) };
+///
+/// // Safety: 72 <= 128.
+/// let m2: __m128i = unsafe { _mm_mask_upto_si128_custom(72) };
+/// # // __m128i doesn't implement PartialEq, so quietly transmute back to arrays.
+/// # let m1: [i64; 2] = unsafe { core::mem::transmute::<__m128i, _>(m1)};
+/// # let m2: [i64; 2] = unsafe { core::mem::transmute::<__m128i, _>(m2)};
+///
+/// assert_eq!(m1, m2);
+/// # }
+/// ```
+///
+/// # Safety
+///
+/// `i` must not be greater than 128. Otherwise, this is UB.
+#[inline(always)]
+pub unsafe fn _mm_mask_upto_si128_custom(i: usize) -> __m128i {
+    // A full 128-bit mask would overflow the shift.
+    let m = 1_u128.checked_shl(i as u32).unwrap_or(0).wrapping_sub(1);
+
+    // Safety: `__m128i` has an exact underlying representation of `u128`.
+    core::mem::transmute::<u128, __m128i>(m)
+}
+
+/// Find number of set bits in 128-bit `__m128i`. SSE has no vector popcount, so this counts
+/// each 64-bit lane with scalar `popcnt`.
+///
+/// # Examples
+///
+/// ```
+/// # use core::arch::x86_64::*;
+/// # use myers_ed::sse::plumbing::_mm_popcnt_si128_custom;
+/// # fn main() {
+/// let a: __m128i = unsafe { core::mem::transmute::<[i64; 2], _>([1, 15]) };
+///
+/// assert_eq!(5, _mm_popcnt_si128_custom(a));
+/// # }
+/// ```
+#[inline(always)]
+pub fn _mm_popcnt_si128_custom(a: __m128i) -> i64 {
+    // Safety: `__m128i` has an exact underlying representation of `[u64; 2]`.
+    let a = unsafe { core::mem::transmute::<__m128i, [u64; 2]>(a) };
+
+    (a[0].count_ones() + a[1].count_ones()) as i64
+}
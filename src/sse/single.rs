@@ -0,0 +1,43 @@
+use core::arch::x86_64::*;
+
+use anyhow::{Result, anyhow};
+
+use crate::generic::single::myers_ed_single_with_peq;
+use crate::peq::{Peq, SingleWordPeq};
+
+/// Perform Myers algorithm to find the edit distance between `a` and `b`. Uses SIMD SSE2 with 128-bit words.
+/// Input bytes `a` must be `<= 128` bytes. Input bytes `b` can be any length.
+///
+/// # Examples
+///
+/// ```
+/// # use myers_ed::sse::single::myers_ed_single_sse;
+/// # fn main() {
+/// let d = myers_ed_single_sse(b"ACCCT", b"ACCTT");
+///
+/// assert_eq!(d, 1);
+/// # }
+/// ```
+pub fn myers_ed_single_sse(a: &[u8], b: &[u8]) -> usize {
+    assert!(a.len() <= 128, "Input must be <= 128 bytes");
+
+    // Infallible: we've verified a.len() <= 128.
+    let peq = SingleWordPeq::from_bytes(a);
+
+    myers_ed_single_sse_with_peq(&peq, b)
+}
+
+pub fn try_myers_ed_single_sse(a: &[u8], b: &[u8]) -> Result<usize> {
+    if a.len() > 128 {
+        return Err(anyhow!("Input must be <= 128 bytes"));
+    }
+
+    // Infallible: we've verified a.len() <= 128.
+    let peq = SingleWordPeq::from_bytes(a);
+
+    Ok(myers_ed_single_sse_with_peq(&peq, b))
+}
+
+pub fn myers_ed_single_sse_with_peq<P: Peq<__m128i>>(peq: &P, b: &[u8]) -> usize {
+    myers_ed_single_with_peq(peq, b)
+}
@@ -0,0 +1,128 @@
+//! [`Word`] implementation for 128-bit SSE vectors.
+
+use core::arch::x86_64::*;
+
+use crate::sse::plumbing::*;
+use crate::word::Word;
+
+impl Word for __m128i {
+    // Safety: `__m128i` is POD so this value is valid.
+    const ZERO: __m128i = unsafe { core::mem::zeroed() };
+
+    // Safety: `__m128i` has an exact underlying representation of `[i64; 2]`.
+    const ONE: __m128i = unsafe { core::mem::transmute::<[i64; 2], _>([1, 0]) };
+
+    // Safety: `__m128i` has an exact underlying representation of `[i64; 2]`.
+    const ONES: __m128i = unsafe { core::mem::transmute::<[i64; 2], _>([-1; 2]) };
+
+    #[inline(always)]
+    unsafe fn bit_at_unchecked(i: usize) -> __m128i {
+        // Safety: `__m128i` has an exact underlying representation of `u128`.
+        core::mem::transmute::<u128, __m128i>(1_u128 << i)
+    }
+
+    #[inline(always)]
+    fn bit_or(self, rhs: __m128i) -> __m128i {
+        // Safety: we guarantee that sse2 is present if sse crate feature compiles.
+        unsafe { _mm_or_si128(self, rhs) }
+    }
+
+    #[inline(always)]
+    fn bit_and(self, rhs: __m128i) -> __m128i {
+        // Safety: we guarantee that sse2 is present if sse crate feature compiles.
+        unsafe { _mm_and_si128(self, rhs) }
+    }
+
+    #[inline(always)]
+    fn bit_xor(self, rhs: __m128i) -> __m128i {
+        // Safety: we guarantee that sse2 is present if sse crate feature compiles.
+        unsafe { _mm_xor_si128(self, rhs) }
+    }
+
+    #[inline(always)]
+    fn bit_not(self) -> __m128i {
+        // Safety: we guarantee that sse2 is present if sse crate feature compiles.
+        unsafe { _mm_xor_si128(self, Self::ONES) }
+    }
+
+    #[inline(always)]
+    fn wide_add(self, rhs: __m128i) -> __m128i {
+        _mm_add_si128_custom(self, rhs)
+    }
+
+    #[inline(always)]
+    fn shl_one(self, carry: bool) -> __m128i {
+        let s = _mm_slli_si128_custom::<1>(self);
+
+        // Safety: we guarantee that sse2 is present if sse crate feature compiles.
+        unsafe { _mm_or_si128(s, _mm_cvtsi64_si128(carry as i64)) }
+    }
+
+    #[inline(always)]
+    fn shr_one(self) -> __m128i {
+        // Safety: we guarantee that sse2 is present if sse crate feature compiles.
+        unsafe {
+            // Shift lane 1 down into lane 0 by bytes, then left shift by 63 to get the bit that
+            // underflowed out of lane 1.
+            let m = _mm_slli_epi64::<63>(_mm_srli_si128::<8>(self));
+
+            _mm_or_si128(_mm_srli_epi64::<1>(self), m)
+        }
+    }
+
+    #[inline(always)]
+    fn msb(self) -> bool {
+        // Safety: we guarantee that sse2 is present if sse crate feature compiles.
+        unsafe { _mm_movemask_pd(_mm_castsi128_pd(self)) >> 1 == 1 }
+    }
+
+    #[inline(always)]
+    unsafe fn popcnt_upto_unchecked(self, n: usize) -> usize {
+        // Safety: caller guarantees that `n <= 128`.
+        let m = _mm_mask_upto_si128_custom(n);
+
+        _mm_popcnt_si128_custom(_mm_and_si128(self, m)) as usize
+    }
+
+    /// Eight 16x8-bit lanes, zero-padded, and a mask of the real bits.
+    type Spread = ([__m128i; 8], __m128i);
+
+    #[inline(always)]
+    unsafe fn spread_bytes_unchecked(s: &[u8]) -> ([__m128i; 8], __m128i) {
+        // Copy into a zero-padded buffer so we can always load full lanes.
+        let mut buf = [0_u8; 128];
+        buf[..s.len()].copy_from_slice(s);
+
+        let p = buf.as_ptr() as *const __m128i;
+        let lanes = core::array::from_fn(|i| _mm_loadu_si128(p.add(i)));
+
+        // Padding bytes compare equal to symbol `0`, so remember which bits are real.
+        (lanes, _mm_mask_upto_si128_custom(s.len()))
+    }
+
+    #[inline(always)]
+    fn eq_mask(spread: &([__m128i; 8], __m128i), c: u8) -> __m128i {
+        // Safety
+        //
+        // The `sse2` `target_feature` must be available.
+        #[inline(always)]
+        unsafe fn __inner_eq_mask(spread: &([__m128i; 8], __m128i), c: u8) -> __m128i {
+            let (lanes, m) = spread;
+
+            // Broadcast symbol across all 16 lanes.
+            let c = _mm_set1_epi8(c as i8);
+
+            // Each 16-byte lane compares into exactly one 16-bit chunk of the result.
+            let mut eq = [0_u16; 8];
+            for (eq, &l) in eq.iter_mut().zip(lanes.iter()) {
+                *eq = _mm_movemask_epi8(_mm_cmpeq_epi8(l, c)) as u16;
+            }
+
+            // Safety: `__m128i` has an exact underlying representation of `[u16; 8]`.
+            _mm_and_si128(core::mem::transmute::<[u16; 8], __m128i>(eq), *m)
+        }
+
+        // Safety: we guarantee that sse2 is present if sse crate feature compiles.
+        unsafe { __inner_eq_mask(spread, c) }
+    }
+}
@@ -0,0 +1,455 @@
+use core::arch::x86_64::*;
+
+/// A fixed-width bitvector that the Myers kernels can run on. Every operation treats the word as
+/// one wide unsigned integer, regardless of how the underlying type splits it into lanes.
+pub trait Word: Copy {
+    /// This word entirely zeroed.
+    const ZERO: Self;
+
+    /// This word's LSB set to 1 and the rest zeroed.
+    const ONE: Self;
+
+    /// This word with every bit set to 1.
+    const ONES: Self;
+
+    /// Compute a bitmask such that bit `i` is set to 1 and the rest of the word is zeroed.
+    ///
+    /// # Safety
+    ///
+    /// Caller must guarantee that `i < 8 * std::mem::size_of::<Self>()`.
+    unsafe fn bit_at_unchecked(i: usize) -> Self;
+
+    /// Test whether bit `i` of `self` is set.
+    ///
+    /// # Safety
+    ///
+    /// Caller must guarantee that `i < 8 * std::mem::size_of::<Self>()`.
+    #[inline(always)]
+    unsafe fn bit_test_unchecked(self, i: usize) -> bool {
+        // Isolate bit `i` and count it. Types with a cheaper test override this.
+        self.bit_and(Self::bit_at_unchecked(i))
+            .popcnt_upto_unchecked(i + 1)
+            == 1
+    }
+
+    /// Compute the bitwise OR operation between `self` and `rhs`. Equivalent to
+    /// [`BitOr`](`std::ops::BitOr`) but has custom implementations in this crate for SIMD types.
+    fn bit_or(self, rhs: Self) -> Self;
+
+    /// Compute the bitwise AND operation between `self` and `rhs`.
+    fn bit_and(self, rhs: Self) -> Self;
+
+    /// Compute the bitwise XOR operation between `self` and `rhs`.
+    fn bit_xor(self, rhs: Self) -> Self;
+
+    /// Compute the bitwise NOT of `self`.
+    fn bit_not(self) -> Self;
+
+    /// Add `self` and `rhs` as full-width unsigned integers, carrying between any internal lanes
+    /// and discarding the carry out of the MSB.
+    fn wide_add(self, rhs: Self) -> Self;
+
+    /// Left shift the full width of `self` by one bit, shifting `carry` in as the new LSB.
+    fn shl_one(self, carry: bool) -> Self;
+
+    /// Right shift the full width of `self` by one bit, shifting in a zero as the new MSB.
+    fn shr_one(self) -> Self;
+
+    /// Right shift the full width of `self` by `n` bits, shifting in zeros.
+    ///
+    /// # Safety
+    ///
+    /// Caller must guarantee that `n < 8 * std::mem::size_of::<Self>()`.
+    #[inline(always)]
+    unsafe fn shr_unchecked(self, n: usize) -> Self {
+        // Shift one bit at a time. Types with a cheaper shift override this.
+        (0..n).fold(self, |s, _| s.shr_one())
+    }
+
+    /// Get the MSB of the full width of `self`.
+    fn msb(self) -> bool;
+
+    /// Count the set bits of `self` below bit `n`.
+    ///
+    /// # Safety
+    ///
+    /// Caller must guarantee that `n <= 8 * std::mem::size_of::<Self>()`.
+    unsafe fn popcnt_upto_unchecked(self, n: usize) -> usize;
+
+    /// Bytes of a string spread into SIMD lanes, ready for repeated comparison against symbols.
+    type Spread;
+
+    /// Spread the bytes of `s` into lanes for [`Word::eq_mask`].
+    ///
+    /// # Safety
+    ///
+    /// Caller must guarantee that `s.len() <= 8 * std::mem::size_of::<Self>()`.
+    unsafe fn spread_bytes_unchecked(s: &[u8]) -> Self::Spread;
+
+    /// Compute a bitmask such that bit `i` is set to 1 if the `i`-th spread byte is equal to `c`
+    /// and the rest of the word is zeroed.
+    fn eq_mask(spread: &Self::Spread, c: u8) -> Self;
+}
+
+/// A SIMD word whose target features are detected at runtime rather than asserted at compile
+/// time. It is crate-private, so its safe [`Word`] operations are only reachable through
+/// [`crate::dispatch`], which only uses them once the required target features are detected.
+#[derive(Clone, Copy)]
+#[repr(transparent)]
+pub(crate) struct Unchecked<T>(pub(crate) T);
+
+impl Word for u64 {
+    const ZERO: u64 = 0;
+    const ONE: u64 = 1;
+    const ONES: u64 = u64::MAX;
+
+    #[inline(always)]
+    unsafe fn bit_at_unchecked(i: usize) -> u64 {
+        1_u64 << i
+    }
+
+    #[inline(always)]
+    unsafe fn bit_test_unchecked(self, i: usize) -> bool {
+        self >> i & 1 == 1
+    }
+
+    #[inline(always)]
+    fn bit_or(self, rhs: u64) -> u64 {
+        self | rhs
+    }
+
+    #[inline(always)]
+    fn bit_and(self, rhs: u64) -> u64 {
+        self & rhs
+    }
+
+    #[inline(always)]
+    fn bit_xor(self, rhs: u64) -> u64 {
+        self ^ rhs
+    }
+
+    #[inline(always)]
+    fn bit_not(self) -> u64 {
+        !self
+    }
+
+    #[inline(always)]
+    fn wide_add(self, rhs: u64) -> u64 {
+        self.wrapping_add(rhs)
+    }
+
+    #[inline(always)]
+    fn shl_one(self, carry: bool) -> u64 {
+        (self << 1_u64) | carry as u64
+    }
+
+    #[inline(always)]
+    fn shr_one(self) -> u64 {
+        self >> 1_u64
+    }
+
+    #[inline(always)]
+    unsafe fn shr_unchecked(self, n: usize) -> u64 {
+        self >> n
+    }
+
+    #[inline(always)]
+    fn msb(self) -> bool {
+        self >> 63 == 1
+    }
+
+    #[inline(always)]
+    unsafe fn popcnt_upto_unchecked(self, n: usize) -> usize {
+        // A full 64-bit mask would overflow the shift.
+        let m = 1_u64.checked_shl(n as u32).unwrap_or(0).wrapping_sub(1);
+
+        (self & m).count_ones() as usize
+    }
+
+    /// Four 16x8-bit SSE2 lanes, zero-padded, and a mask of the real bits.
+    type Spread = ([__m128i; 4], u64);
+
+    #[inline(always)]
+    unsafe fn spread_bytes_unchecked(s: &[u8]) -> ([__m128i; 4], u64) {
+        // Copy into a zero-padded buffer so we can always load full lanes.
+        let mut buf = [0_u8; 64];
+        buf[..s.len()].copy_from_slice(s);
+
+        // Padding bytes compare equal to symbol `0`, so remember which bits are real.
+        let m = 1_u64
+            .checked_shl(s.len() as u32)
+            .unwrap_or(0)
+            .wrapping_sub(1);
+
+        let p = buf.as_ptr() as *const __m128i;
+
+        (
+            [
+                _mm_loadu_si128(p),
+                _mm_loadu_si128(p.add(1)),
+                _mm_loadu_si128(p.add(2)),
+                _mm_loadu_si128(p.add(3)),
+            ],
+            m,
+        )
+    }
+
+    #[inline(always)]
+    fn eq_mask(spread: &([__m128i; 4], u64), c: u8) -> u64 {
+        // Safety
+        //
+        // The `sse2` `target_feature` must be available.
+        #[inline(always)]
+        unsafe fn __inner_eq_mask(spread: &([__m128i; 4], u64), c: u8) -> u64 {
+            let (lanes, m) = spread;
+
+            // Broadcast symbol across all 16 lanes.
+            let c = _mm_set1_epi8(c as i8);
+
+            // Compare each 16-byte lane against the symbol and gather the byte-wise results into
+            // a 16-bit mask. Infallible: `_mm_movemask_epi8` only ever sets the low 16 bits.
+            let mut eq = 0_u64;
+            for (i, &l) in lanes.iter().enumerate() {
+                eq |= (_mm_movemask_epi8(_mm_cmpeq_epi8(l, c)) as u64) << (16 * i);
+            }
+
+            eq & m
+        }
+
+        // Safety: sse2 is part of the x86_64 baseline, so it is always available.
+        unsafe { __inner_eq_mask(spread, c) }
+    }
+}
+
+impl Word for u128 {
+    const ZERO: u128 = 0;
+    const ONE: u128 = 1;
+    const ONES: u128 = u128::MAX;
+
+    #[inline(always)]
+    unsafe fn bit_at_unchecked(i: usize) -> u128 {
+        1_u128 << i
+    }
+
+    #[inline(always)]
+    unsafe fn bit_test_unchecked(self, i: usize) -> bool {
+        self >> i & 1 == 1
+    }
+
+    #[inline(always)]
+    fn bit_or(self, rhs: u128) -> u128 {
+        self | rhs
+    }
+
+    #[inline(always)]
+    fn bit_and(self, rhs: u128) -> u128 {
+        self & rhs
+    }
+
+    #[inline(always)]
+    fn bit_xor(self, rhs: u128) -> u128 {
+        self ^ rhs
+    }
+
+    #[inline(always)]
+    fn bit_not(self) -> u128 {
+        !self
+    }
+
+    #[inline(always)]
+    fn wide_add(self, rhs: u128) -> u128 {
+        self.wrapping_add(rhs)
+    }
+
+    #[inline(always)]
+    fn shl_one(self, carry: bool) -> u128 {
+        (self << 1_u128) | carry as u128
+    }
+
+    #[inline(always)]
+    fn shr_one(self) -> u128 {
+        self >> 1_u128
+    }
+
+    #[inline(always)]
+    unsafe fn shr_unchecked(self, n: usize) -> u128 {
+        self >> n
+    }
+
+    #[inline(always)]
+    fn msb(self) -> bool {
+        self >> 127 == 1
+    }
+
+    #[inline(always)]
+    unsafe fn popcnt_upto_unchecked(self, n: usize) -> usize {
+        // A full 128-bit mask would overflow the shift.
+        let m = 1_u128.checked_shl(n as u32).unwrap_or(0).wrapping_sub(1);
+
+        (self & m).count_ones() as usize
+    }
+
+    /// The low and high 64 bytes, each spread as for a `u64`.
+    type Spread = [<u64 as Word>::Spread; 2];
+
+    #[inline(always)]
+    unsafe fn spread_bytes_unchecked(s: &[u8]) -> [<u64 as Word>::Spread; 2] {
+        // Safety: caller guarantees `s.len() <= 128`, so each half is at most 64 bytes.
+        let (lo, hi) = s.split_at(s.len().min(64));
+
+        [
+            u64::spread_bytes_unchecked(lo),
+            u64::spread_bytes_unchecked(hi),
+        ]
+    }
+
+    #[inline(always)]
+    fn eq_mask(spread: &[<u64 as Word>::Spread; 2], c: u8) -> u128 {
+        let lo = u64::eq_mask(&spread[0], c) as u128;
+        let hi = u64::eq_mask(&spread[1], c) as u128;
+
+        lo | (hi << 64)
+    }
+}
+
+/// A `64 * N`-bit unsigned integer, stored as `N` little-endian `u64` limbs. Useful for patterns
+/// slightly too long for a `u128`, without requiring any SIMD extensions.
+impl<const N: usize> Word for [u64; N] {
+    const ZERO: [u64; N] = [0; N];
+
+    const ONE: [u64; N] = {
+        let mut a = [0; N];
+        if N > 0 {
+            a[0] = 1;
+        }
+        a
+    };
+
+    const ONES: [u64; N] = [u64::MAX; N];
+
+    #[inline(always)]
+    unsafe fn bit_at_unchecked(i: usize) -> [u64; N] {
+        let mut a = [0; N];
+
+        // Set bit inside selected limb.
+        *a.get_unchecked_mut(i >> 6) = 1_u64 << (i & 63);
+
+        a
+    }
+
+    #[inline(always)]
+    unsafe fn bit_test_unchecked(self, i: usize) -> bool {
+        // Test bit inside selected limb.
+        *self.get_unchecked(i >> 6) >> (i & 63) & 1 == 1
+    }
+
+    #[inline(always)]
+    fn bit_or(self, rhs: [u64; N]) -> [u64; N] {
+        core::array::from_fn(|i| self[i] | rhs[i])
+    }
+
+    #[inline(always)]
+    fn bit_and(self, rhs: [u64; N]) -> [u64; N] {
+        core::array::from_fn(|i| self[i] & rhs[i])
+    }
+
+    #[inline(always)]
+    fn bit_xor(self, rhs: [u64; N]) -> [u64; N] {
+        core::array::from_fn(|i| self[i] ^ rhs[i])
+    }
+
+    #[inline(always)]
+    fn bit_not(self) -> [u64; N] {
+        self.map(|x| !x)
+    }
+
+    #[inline(always)]
+    fn wide_add(self, rhs: [u64; N]) -> [u64; N] {
+        let mut s = [0; N];
+        let mut carry = false;
+
+        // Ripple-carry through the limbs. At most one of the two additions can overflow, since
+        // `(2^64 - 1) + (2^64 - 1) + 1 < 2^65`.
+        for i in 0..N {
+            let (x, c1) = self[i].overflowing_add(rhs[i]);
+            let (x, c2) = x.overflowing_add(carry as u64);
+
+            s[i] = x;
+            carry = c1 | c2;
+        }
+
+        s
+    }
+
+    #[inline(always)]
+    fn shl_one(self, mut carry: bool) -> [u64; N] {
+        let mut s = [0; N];
+
+        // Shift each limb, moving its MSB into the LSB of the next limb.
+        for i in 0..N {
+            s[i] = (self[i] << 1_u64) | carry as u64;
+            carry = self[i] >> 63 == 1;
+        }
+
+        s
+    }
+
+    #[inline(always)]
+    fn shr_one(self) -> [u64; N] {
+        // Shift each limb, moving the LSB of the next limb into its MSB.
+        core::array::from_fn(|i| (self[i] >> 1_u64) | self.get(i + 1).map_or(0, |&x| x << 63))
+    }
+
+    #[inline(always)]
+    unsafe fn shr_unchecked(self, n: usize) -> [u64; N] {
+        let (q, r) = (n >> 6, (n & 63) as u32);
+
+        // Shift whole limbs down by `q`, then bits by `r`, moving the low bits of the next limb
+        // into the top of each. A zero `r` would overflow the shift, so it moves nothing.
+        core::array::from_fn(|i| {
+            let lo = self.get(i + q).copied().unwrap_or(0);
+            let hi = self.get(i + q + 1).copied().unwrap_or(0);
+
+            (lo >> r) | hi.checked_shl(64 - r).unwrap_or(0)
+        })
+    }
+
+    #[inline(always)]
+    fn msb(self) -> bool {
+        self.last().is_some_and(|&x| x >> 63 == 1)
+    }
+
+    #[inline(always)]
+    unsafe fn popcnt_upto_unchecked(self, n: usize) -> usize {
+        let mut popcnt = 0;
+
+        for (i, &x) in self.iter().enumerate() {
+            // Number of real bits in this limb. A full 64-bit mask would overflow the shift.
+            let bits = n.saturating_sub(64 * i).min(64);
+            let m = 1_u64.checked_shl(bits as u32).unwrap_or(0).wrapping_sub(1);
+
+            popcnt += (x & m).count_ones() as usize;
+        }
+
+        popcnt
+    }
+
+    /// Each 64-byte limb spread as for a `u64`.
+    type Spread = [<u64 as Word>::Spread; N];
+
+    #[inline(always)]
+    unsafe fn spread_bytes_unchecked(s: &[u8]) -> [<u64 as Word>::Spread; N] {
+        core::array::from_fn(|i| {
+            // Safety: caller guarantees `s.len() <= 64 * N`, so each limb is at most 64 bytes.
+            let lo = (64 * i).min(s.len());
+            let hi = (64 * (i + 1)).min(s.len());
+
+            u64::spread_bytes_unchecked(&s[lo..hi])
+        })
+    }
+
+    #[inline(always)]
+    fn eq_mask(spread: &[<u64 as Word>::Spread; N], c: u8) -> [u64; N] {
+        spread.each_ref().map(|s| u64::eq_mask(s, c))
+    }
+}
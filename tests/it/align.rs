@@ -0,0 +1,85 @@
+use myers_ed::generic::align::Alignment;
+use myers_ed::mode::Mode;
+use myers_ed::scalar::single::myers_ed_single_scalar_align;
+
+use crate::common::{Rng, naive_with_mode, pairs};
+
+// Check that `aln` is a valid alignment of `a` to `b[start..end]` whose cost is its distance, and
+// that its distance and end match `naive_with_mode`.
+fn check_alignment(a: &[u8], b: &[u8], mode: Mode, aln: &Alignment) {
+    let ctx = format!(
+        "a.len() = {}, b.len() = {}, mode = {mode:?}",
+        a.len(),
+        b.len()
+    );
+
+    assert_eq!(
+        (aln.distance, aln.end),
+        naive_with_mode(a, b, mode),
+        "{ctx}"
+    );
+    assert_eq!(aln.start, 0, "{ctx}");
+
+    let (mut i, mut j, mut cost) = (0, aln.start, 0);
+    let mut last = None;
+
+    let mut cigar = aln.cigar.as_str();
+    while !cigar.is_empty() {
+        let digits = cigar.find(|c: char| !c.is_ascii_digit()).expect(&ctx);
+        let len: usize = cigar[..digits].parse().expect(&ctx);
+        let op = cigar.as_bytes()[digits];
+        cigar = &cigar[digits + 1..];
+
+        // Runs are maximal and never empty.
+        assert!(len > 0 && last != Some(op), "{ctx}, cigar = {}", aln.cigar);
+        last = Some(op);
+
+        for _ in 0..len {
+            match op {
+                b'=' | b'X' => {
+                    assert_eq!(a[i] == b[j], op == b'=', "{ctx}, cigar = {}", aln.cigar);
+                    (i, j) = (i + 1, j + 1);
+                }
+                b'I' => i += 1,
+                b'D' => j += 1,
+                _ => panic!("{ctx}, cigar = {}", aln.cigar),
+            }
+
+            cost += (op != b'=') as usize;
+        }
+    }
+
+    assert_eq!((i, j), (a.len(), aln.end), "{ctx}, cigar = {}", aln.cigar);
+    assert_eq!(cost, aln.distance, "{ctx}, cigar = {}", aln.cigar);
+}
+
+// Check `f` in both modes on every pair from `pairs`, and in prefix mode also with a random tail
+// on the text.
+fn check_align(max_a: usize, f: impl Fn(&[u8], &[u8], Mode) -> Alignment) {
+    let mut rng = Rng::new(25);
+
+    for (a, b) in pairs(max_a) {
+        let tail = [b.as_slice(), &rng.bytes(50)].concat();
+
+        for (mode, b) in [
+            (Mode::Global, &b),
+            (Mode::Prefix, &b),
+            (Mode::Prefix, &tail),
+        ] {
+            check_alignment(&a, b, mode, &f(&a, b, mode));
+        }
+    }
+}
+
+#[test]
+fn align_scalar_matches_naive() {
+    check_align(64, myers_ed_single_scalar_align);
+}
+
+#[test]
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
+fn align_avx512_matches_naive() {
+    use myers_ed::avx512::single::myers_ed_single_avx512_align;
+
+    check_align(512, myers_ed_single_avx512_align);
+}
@@ -0,0 +1,61 @@
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
+use myers_ed::avx512::banded::myers_ed_banded_avx512;
+use myers_ed::generic::banded::myers_ed_banded;
+use myers_ed::scalar::banded::myers_ed_banded_scalar;
+
+use crate::common::{Rng, check_bounded, naive_ed};
+
+#[test]
+fn banded_scalar_matches_naive() {
+    check_bounded(usize::MAX, 31, myers_ed_banded_scalar);
+}
+
+#[test]
+fn banded_u128_matches_naive() {
+    check_bounded(usize::MAX, 63, myers_ed_banded::<u128>);
+}
+
+#[test]
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
+fn banded_avx512_matches_naive() {
+    check_bounded(usize::MAX, 255, myers_ed_banded_avx512);
+    check_bounded(
+        usize::MAX,
+        255,
+        myers_ed_banded::<core::arch::x86_64::__m512i>,
+    );
+}
+
+// `2k` overflows for huge `k`, so the band check must not multiply it.
+#[test]
+#[should_panic(expected = "Band of width 2k + 1 must fit in one word")]
+fn banded_huge_k_panics() {
+    myers_ed_banded::<u64>(b"ACGT", b"ACGT", usize::MAX);
+}
+
+#[test]
+#[should_panic(expected = "Band of width 2k + 1 must fit in one word")]
+fn banded_band_wider_than_word_panics() {
+    myers_ed_banded::<u64>(b"ACGT", b"ACGT", 32);
+}
+
+// Long pairs a few edits apart, so the band slides far past where each symbol's equality bits
+// were last shifted, and rows keep entering it long after the start.
+#[test]
+fn banded_scalar_long_matches_naive() {
+    let mut rng = Rng::new(22);
+
+    for edits in [0, 1, 5, 20] {
+        let a = rng.bytes(3000);
+        let b = rng.mutate(&a, edits);
+        let d = naive_ed(&a, &b);
+
+        for k in [d.saturating_sub(1), d, 31] {
+            assert_eq!(
+                myers_ed_banded_scalar(&a, &b, k),
+                (d <= k).then_some(d),
+                "edits = {edits}, k = {k}"
+            );
+        }
+    }
+}
@@ -0,0 +1,158 @@
+use myers_ed::avx512::batch::{
+    myers_ed_batch_avx512, myers_ed_batch_avx512_with_peq, myers_ed_batch_stream_avx512,
+    myers_ed_batch16_avx512,
+};
+use myers_ed::peq::SingleWordPeq;
+
+use crate::common::{LENGTHS, Rng, naive_ed, pairs};
+
+// Groups of `N` pairs with patterns of at most `max_a` bytes, padded with empty pairs.
+fn groups<const N: usize>(max_a: usize) -> Vec<[(Vec<u8>, Vec<u8>); N]> {
+    let pairs = pairs(max_a);
+
+    pairs
+        .chunks(N)
+        .map(|c| core::array::from_fn(|i| c.get(i).cloned().unwrap_or_default()))
+        .collect()
+}
+
+fn check<const N: usize>(group: &[(Vec<u8>, Vec<u8>); N], d: [usize; N]) {
+    for ((a, b), d) in group.iter().zip(d) {
+        assert_eq!(
+            d,
+            naive_ed(a, b),
+            "a.len() = {}, b.len() = {}",
+            a.len(),
+            b.len()
+        );
+    }
+}
+
+#[test]
+fn batch_avx512_matches_naive() {
+    for group in groups::<8>(64) {
+        let d = myers_ed_batch_avx512(
+            group.each_ref().map(|(a, _)| &a[..]),
+            group.each_ref().map(|(_, b)| &b[..]),
+        );
+
+        check(&group, d);
+    }
+}
+
+#[test]
+fn batch_avx512_with_separate_peqs_matches_naive() {
+    for group in groups::<8>(64) {
+        // Separate allocations, so the lanes gather from unrelated addresses.
+        let peqs = group
+            .each_ref()
+            .map(|(a, _)| Box::new(SingleWordPeq::<u64>::from_bytes(a)));
+        let d = myers_ed_batch_avx512_with_peq(
+            peqs.each_ref().map(|p| &**p),
+            group.each_ref().map(|(_, b)| &b[..]),
+        );
+
+        check(&group, d);
+    }
+}
+
+#[test]
+fn batch16_avx512_matches_naive() {
+    // Sixteen lanes hold patterns of at most 32 bytes, so add the lengths around a 32-bit lane.
+    let mut rng = Rng::new(16);
+    let mut pairs = Vec::new();
+
+    for la in [0, 1, 31, 32] {
+        for lb in LENGTHS {
+            pairs.push((rng.bytes(la), rng.bytes(lb)));
+        }
+
+        let a = rng.bytes(la);
+        pairs.push((a.clone(), rng.mutate(&a, 2)));
+    }
+
+    for c in pairs.chunks(16) {
+        let group: [(Vec<u8>, Vec<u8>); 16] =
+            core::array::from_fn(|i| c.get(i).cloned().unwrap_or_default());
+        let d = myers_ed_batch16_avx512(
+            group.each_ref().map(|(a, _)| &a[..]),
+            group.each_ref().map(|(_, b)| &b[..]),
+        );
+
+        check(&group, d);
+    }
+}
+
+#[test]
+fn batch_stream_avx512_matches_naive() {
+    // Text lengths vary from 0 to 513 bytes, so lanes finish and refill at different columns.
+    let pairs = pairs(64);
+
+    let mut d: Vec<_> = myers_ed_batch_stream_avx512(pairs.iter().cloned()).collect();
+    d.sort();
+
+    assert_eq!(d.len(), pairs.len());
+
+    for ((a, b), (i, d)) in pairs.iter().zip(d) {
+        assert_eq!(
+            d,
+            naive_ed(a, b),
+            "pair {i}, a.len() = {}, b.len() = {}",
+            a.len(),
+            b.len()
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "avx512-vbmi")]
+fn batch_avx512_with_alphabet_matches_naive() {
+    use myers_ed::avx512::batch::myers_ed_batch_avx512_with_alphabet;
+    use myers_ed::peq::BitAlphabet;
+
+    // Bytes outside the alphabet never match, so give them distinct stand-ins in `a` and `b`.
+    fn naive(a: &[u8], b: &[u8], alphabet: BitAlphabet) -> usize {
+        let a: Vec<_> = a
+            .iter()
+            .map(|&c| if alphabet.contains(c) { c } else { 0xFE })
+            .collect();
+        let b: Vec<_> = b
+            .iter()
+            .map(|&c| if alphabet.contains(c) { c } else { 0xFF })
+            .collect();
+
+        naive_ed(&a, &b)
+    }
+
+    let mut rng = Rng::new(18);
+    let mut groups = groups::<8>(64);
+
+    // Sprinkle `N`s into both sides, which are only in `DNA_N`.
+    for (a, b) in groups.iter_mut().flatten() {
+        for s in [a, b] {
+            for _ in 0..s.len() / 8 {
+                let i = rng.below(s.len());
+                s[i] = b'N';
+            }
+        }
+    }
+
+    // `PRINTABLE_ASCII` has more than 64 symbols, so it takes the gather fallback.
+    for alphabet in [
+        BitAlphabet::DNA,
+        BitAlphabet::DNA_N,
+        BitAlphabet::PRINTABLE_ASCII,
+    ] {
+        for group in &groups {
+            let d = myers_ed_batch_avx512_with_alphabet(
+                group.each_ref().map(|(a, _)| &a[..]),
+                group.each_ref().map(|(_, b)| &b[..]),
+                alphabet,
+            );
+
+            for ((a, b), d) in group.iter().zip(d) {
+                assert_eq!(d, naive(a, b, alphabet), "a = {a:?}, b = {b:?}");
+            }
+        }
+    }
+}
@@ -0,0 +1,156 @@
+//! Helpers shared by the differential tests, which check every kernel against a textbook DP on
+//! random inputs at the lengths where word and block boundaries sit.
+
+use myers_ed::mode::Mode;
+
+/// Lengths around the 64-bit word and the 512-bit block, and the empty string.
+pub const LENGTHS: [usize; 7] = [0, 63, 64, 65, 511, 512, 513];
+
+/// Xorshift generator, so failures reproduce from the seed alone.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng(seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) | 1)
+    }
+
+    pub fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    pub fn bytes(&mut self, len: usize) -> Vec<u8> {
+        (0..len).map(|_| b"ACGT"[self.below(4)]).collect()
+    }
+
+    /// Eight 64-bit lanes, biased towards all zeros and all ones so that carries start and ripple
+    /// across many lanes.
+    #[cfg(any(
+        feature = "avx512-emulated",
+        all(feature = "portable-simd", feature = "avx512")
+    ))]
+    pub fn lanes(&mut self) -> [u64; 8] {
+        core::array::from_fn(|_| match self.below(4) {
+            0 => 0,
+            1 => u64::MAX,
+            2 => self.next() >> self.below(64),
+            _ => self.next(),
+        })
+    }
+
+    /// A copy of `s` with `edits` random substitutions, insertions and deletions. Close pairs
+    /// keep the distance small, which is where off-by-one errors in the kernels show.
+    pub fn mutate(&mut self, s: &[u8], edits: usize) -> Vec<u8> {
+        let mut s = s.to_vec();
+
+        for _ in 0..edits {
+            let i = self.below(s.len() + 1);
+            let c = b"ACGT"[self.below(4)];
+
+            match self.below(3) {
+                0 if i < s.len() => s[i] = c,
+                1 if i < s.len() => _ = s.remove(i),
+                _ => s.insert(i, c),
+            }
+        }
+
+        s
+    }
+}
+
+/// Pairs of a pattern of at most `max_a` bytes and a text. Covers every combination of
+/// [`LENGTHS`], both unrelated and with the text a few edits away from the pattern.
+pub fn pairs(max_a: usize) -> Vec<(Vec<u8>, Vec<u8>)> {
+    let mut rng = Rng::new(max_a as u64);
+    let mut pairs = Vec::new();
+
+    for la in LENGTHS.into_iter().filter(|&la| la <= max_a) {
+        for lb in LENGTHS {
+            pairs.push((rng.bytes(la), rng.bytes(lb)));
+        }
+
+        let a = rng.bytes(la);
+
+        for edits in [0, 1, 3, 10] {
+            let b = rng.mutate(&a, edits);
+            pairs.push((a.clone(), b));
+        }
+    }
+
+    pairs
+}
+
+/// Last row of the DP matrix of `a` against `b`, i.e. the distance of `a` to `b[..j]` at index
+/// `j`. With `free_start`, the first row is zero, so a match may start anywhere in `b`.
+pub fn naive_last_row(a: &[u8], b: &[u8], free_start: bool) -> Vec<usize> {
+    let mut row: Vec<usize> = (0..=b.len())
+        .map(|j| if free_start { 0 } else { j })
+        .collect();
+
+    for (i, &x) in (1..).zip(a) {
+        let mut diag = row[0];
+        row[0] = i;
+
+        for (j, &y) in (1..).zip(b) {
+            let up = row[j];
+            row[j] = (diag + (x != y) as usize).min(up + 1).min(row[j - 1] + 1);
+            diag = up;
+        }
+    }
+
+    row
+}
+
+/// Edit distance between `a` and `b`.
+pub fn naive_ed(a: &[u8], b: &[u8]) -> usize {
+    naive_last_row(a, b, false)[b.len()]
+}
+
+/// Check `f` against [`naive_ed`] on every pair from [`pairs`].
+pub fn check_ed(max_a: usize, f: impl Fn(&[u8], &[u8]) -> usize) {
+    for (a, b) in pairs(max_a) {
+        assert_eq!(
+            f(&a, &b),
+            naive_ed(&a, &b),
+            "a.len() = {}, b.len() = {}",
+            a.len(),
+            b.len()
+        );
+    }
+}
+
+/// Check that `f` returns the distance from [`naive_ed`] exactly when it is at most `k`, on every
+/// pair from [`pairs`] and for each `k <= max_k` around that distance.
+pub fn check_bounded(max_a: usize, max_k: usize, f: impl Fn(&[u8], &[u8], usize) -> Option<usize>) {
+    for (a, b) in pairs(max_a) {
+        let d = naive_ed(&a, &b);
+        let ks = [0, d.saturating_sub(1), d, d + 1, max_k];
+
+        for k in ks.into_iter().filter(|&k| k <= max_k) {
+            assert_eq!(
+                f(&a, &b, k),
+                (d <= k).then_some(d),
+                "a.len() = {}, b.len() = {}, k = {k}",
+                a.len(),
+                b.len()
+            );
+        }
+    }
+}
+
+/// Distance of `a` to `b` as `mode` says, and the end of the part of `b` that `a` is aligned to.
+/// In prefix mode, this is the earliest end at the smallest distance.
+pub fn naive_with_mode(a: &[u8], b: &[u8], mode: Mode) -> (usize, usize) {
+    let row = naive_last_row(a, b, false);
+
+    match mode {
+        Mode::Global => (row[b.len()], b.len()),
+        Mode::Prefix => (0..=b.len()).map(|j| (row[j], j)).min().unwrap(),
+    }
+}
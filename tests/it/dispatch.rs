@@ -0,0 +1,48 @@
+use myers_ed::dispatch::{Backend, DispatchPeq, myers_ed, myers_ed_with_peq};
+
+use crate::common::{Rng, check_ed, naive_ed};
+
+// Both sides of each switch between the single and multi-word Peq: 64 for the scalar word, 256 for
+// `Avx2` and `Avx2Multi`, and 512 for `Avx512` and `Avx512Multi`. Only the arms of the backend
+// that `Backend::detect` picks on this CPU run.
+const DISPATCH_LENGTHS: [usize; 7] = [0, 64, 65, 256, 257, 512, 513];
+
+#[test]
+fn dispatch_matches_naive() {
+    check_ed(usize::MAX, myers_ed);
+}
+
+#[test]
+fn dispatch_peq_reuse_matches_naive() {
+    let mut rng = Rng::new(11);
+
+    for la in DISPATCH_LENGTHS {
+        let a = rng.bytes(la);
+        let peq = DispatchPeq::from_bytes(&a);
+
+        assert_eq!(peq.len(), la);
+
+        for b in [rng.bytes(la), rng.mutate(&a, 3), a.clone(), Vec::new()] {
+            assert_eq!(
+                myers_ed_with_peq(&peq, &b),
+                naive_ed(&a, &b),
+                "a.len() = {la}, b.len() = {}",
+                b.len()
+            );
+        }
+    }
+}
+
+#[test]
+fn dispatch_peq_backend() {
+    for la in DISPATCH_LENGTHS {
+        let peq = DispatchPeq::from_bytes(vec![b'A'; la]);
+        let expected = if la <= 64 {
+            Backend::Scalar
+        } else {
+            Backend::detect()
+        };
+
+        assert_eq!(peq.backend(), expected, "a.len() = {la}");
+    }
+}
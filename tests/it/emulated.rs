@@ -0,0 +1,211 @@
+use core::arch::x86_64::__m512i;
+
+use myers_ed::avx512::emulated;
+
+use crate::common::Rng;
+
+fn lanes(a: __m512i) -> [u64; 8] {
+    // Safety: `__m512i` has an exact underlying representation of `[u64; 8]`.
+    unsafe { core::mem::transmute::<__m512i, [u64; 8]>(a) }
+}
+
+fn to(a: [u64; 8]) -> __m512i {
+    // Safety: `__m512i` has an exact underlying representation of `[u64; 8]`.
+    unsafe { core::mem::transmute::<[u64; 8], __m512i>(a) }
+}
+
+// Run every emulated intrinsic, or its `core::arch` counterpart, on the same inputs. `c` shares
+// some bytes with `a`, so that the byte comparisons see both outcomes.
+macro_rules! intrinsics {
+    ($(#[$attr:meta])* $name:ident, $($arch:ident)::+) => {
+        $(#[$attr])*
+        unsafe fn $name(
+            a: __m512i,
+            b: __m512i,
+            c: __m512i,
+            k: u64,
+            mem: &[u8; 64],
+        ) -> Vec<[u64; 8]> {
+            use $($arch)::+::*;
+
+            // Safety: the caller guarantees the target features, and the loads only read `mem`,
+            // which is 64 bytes long.
+            unsafe {
+                let mask = |m: u64| [m, 0, 0, 0, 0, 0, 0, 0];
+                let k8 = k as u8;
+                let a0 = lanes(a)[0] as i64;
+
+                vec![
+                    lanes(_mm512_setzero_si512()),
+                    lanes(_mm512_set1_epi64(a0)),
+                    lanes(_mm512_set1_epi8(a0 as i8)),
+                    lanes(_mm512_mask_set1_epi64(b, k8, a0)),
+                    lanes(_mm512_maskz_set1_epi64(k8, a0)),
+                    lanes(_mm512_add_epi64(a, b)),
+                    lanes(_mm512_mask_sub_epi64(c, k8, a, b)),
+                    lanes(_mm512_slli_epi64::<13>(a)),
+                    lanes(_mm512_srli_epi64::<13>(a)),
+                    lanes(_mm512_sllv_epi64(a, _mm512_srli_epi64::<57>(b))),
+                    lanes(_mm512_srlv_epi64(a, _mm512_srli_epi64::<57>(b))),
+                    lanes(_mm512_maskz_permutexvar_epi64(k8, b, a)),
+                    lanes(_mm512_alignr_epi64::<0>(a, b)),
+                    lanes(_mm512_alignr_epi64::<3>(a, b)),
+                    lanes(_mm512_alignr_epi64::<7>(a, b)),
+                    lanes(_mm512_or_si512(a, b)),
+                    lanes(_mm512_and_si512(a, b)),
+                    lanes(_mm512_xor_si512(a, b)),
+                    lanes(_mm512_mask_or_epi64(c, k8, a, b)),
+                    lanes(_mm512_ternarylogic_epi64::<0x55>(a, b, c)),
+                    lanes(_mm512_ternarylogic_epi64::<0x96>(a, b, c)),
+                    lanes(_mm512_ternarylogic_epi64::<0xE8>(a, b, c)),
+                    lanes(_mm512_ternarylogic_epi64::<0xCA>(a, b, c)),
+                    mask(_mm512_cmp_epu64_mask::<0>(a, c) as u64),
+                    mask(_mm512_cmp_epu64_mask::<_MM_CMPINT_LT>(a, b) as u64),
+                    mask(_mm512_cmp_epu64_mask::<2>(a, b) as u64),
+                    mask(_mm512_cmp_epu64_mask::<4>(a, c) as u64),
+                    mask(_mm512_cmp_epu64_mask::<5>(a, b) as u64),
+                    mask(_mm512_cmp_epu64_mask::<6>(a, b) as u64),
+                    mask(_mm512_test_epi64_mask(a, b) as u64),
+                    mask(_mm512_cmpeq_epi64_mask(a, c) as u64),
+                    mask(_mm512_cmplt_epi64_mask(a, b) as u64),
+                    mask(_mm512_reduce_add_epi64(a) as u64),
+                    lanes(_mm512_maskz_loadu_epi8(k, mem.as_ptr().cast())),
+                    mask(_mm512_mask_cmpeq_epi8_mask(k, a, c)),
+                ]
+            }
+        }
+    };
+}
+
+intrinsics!(run_emulated, emulated);
+intrinsics!(
+    // Safety
+    //
+    // The `avx512f` and `avx512bw` `target_features` must be available.
+    #[target_feature(enable = "avx512f,avx512bw")]
+    run_hardware,
+    core::arch::x86_64
+);
+
+#[test]
+fn emulated_intrinsics_match_hardware() {
+    if !is_x86_feature_detected!("avx512f") || !is_x86_feature_detected!("avx512bw") {
+        return;
+    }
+
+    let mut rng = Rng::new(19);
+
+    for _ in 0..10_000 {
+        let (a, b) = (rng.lanes(), rng.lanes());
+        let c = a.map(|x| x ^ (rng.next() & rng.next() & rng.next()));
+        let k = rng.next();
+        let mem: [u8; 64] = core::array::from_fn(|_| rng.next() as u8);
+
+        // Safety: avx512f and avx512bw were detected above, and every intrinsic here only reads
+        // `mem`, which is 64 bytes long.
+        let (e, h) = unsafe {
+            (
+                run_emulated(to(a), to(b), to(c), k, &mem),
+                run_hardware(to(a), to(b), to(c), k, &mem),
+            )
+        };
+
+        for (i, (e, h)) in e.iter().zip(&h).enumerate() {
+            assert_eq!(
+                e, h,
+                "intrinsic {i} differs on a = {a:x?}, b = {b:x?}, k = {k:x}"
+            );
+        }
+    }
+}
+
+#[test]
+fn emulated_popcnt_matches_hardware() {
+    // Safety
+    //
+    // The `avx512f` and `avx512vpopcntdq` `target_features` must be available.
+    #[target_feature(enable = "avx512f,avx512vpopcntdq")]
+    unsafe fn hardware(a: __m512i) -> [u64; 8] {
+        lanes(core::arch::x86_64::_mm512_popcnt_epi64(a))
+    }
+
+    if !is_x86_feature_detected!("avx512f") || !is_x86_feature_detected!("avx512vpopcntdq") {
+        return;
+    }
+
+    let mut rng = Rng::new(20);
+
+    for _ in 0..10_000 {
+        let a = to(rng.lanes());
+
+        // Safety: avx512f and avx512vpopcntdq were detected above.
+        assert_eq!(lanes(unsafe { emulated::_mm512_popcnt_epi64(a) }), unsafe {
+            hardware(a)
+        });
+    }
+}
+
+// The tests below run `plumbing` and the kernels on the emulation, so they are only meaningful
+// when it stands in for the real intrinsics.
+
+#[test]
+#[cfg(not(feature = "avx512"))]
+fn emulated_dispatch_skips_emulation() {
+    use myers_ed::dispatch::{Backend, DispatchPeq};
+
+    assert_ne!(Backend::detect(), Backend::Avx512);
+    assert_ne!(
+        DispatchPeq::from_bytes([b'A'; 300]).backend(),
+        Backend::Avx512
+    );
+}
+
+#[test]
+#[cfg(not(feature = "avx512"))]
+fn emulated_plumbing_matches_wide_word() {
+    use myers_ed::avx512::plumbing::*;
+    use myers_ed::word::Word;
+
+    let mut rng = Rng::new(21);
+
+    for _ in 0..10_000 {
+        let (a, b) = (rng.lanes(), rng.lanes());
+        let sum = a.wide_add(b);
+
+        assert_eq!(lanes(_mm512_add_si512_custom::<1>(to(a), to(b))), sum);
+        assert_eq!(lanes(_mm512_add_si512_lookahead_custom(to(a), to(b))), sum);
+        assert_eq!(
+            lanes(_mm512_slli_si512_custom::<1>(to(a))),
+            a.shl_one(false)
+        );
+        assert_eq!(
+            _mm512_popcnt_si512_custom(to(a)) as u32,
+            a.iter().map(|l| l.count_ones()).sum::<u32>(),
+        );
+
+        let n = rng.below(513);
+        // Safety: n <= 512.
+        let m = lanes(unsafe { _mm512_mask_upto_si512_custom(n) });
+        let bit = if n < 512 {
+            // Safety: n < 512.
+            unsafe { <[u64; 8]>::bit_at_unchecked(n) }
+        } else {
+            <[u64; 8]>::ZERO
+        };
+
+        assert_eq!(m.iter().map(|l| l.count_ones()).sum::<u32>() as usize, n);
+        assert_eq!(m.wide_add([1, 0, 0, 0, 0, 0, 0, 0]), bit);
+    }
+}
+
+#[test]
+#[cfg(not(feature = "avx512"))]
+fn emulated_kernels_match_naive() {
+    use myers_ed::avx512::multi::myers_ed_multi_avx512;
+    use myers_ed::avx512::single::myers_ed_single_avx512;
+
+    use crate::common::check_ed;
+
+    check_ed(512, myers_ed_single_avx512);
+    check_ed(usize::MAX, myers_ed_multi_avx512);
+}
@@ -0,0 +1,76 @@
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
+use core::arch::x86_64::__m512i;
+
+use myers_ed::generic::single::myers_ed_single_with_peq;
+use myers_ed::peq::{CompactPeq, SingleWordPeq};
+use myers_ed::scalar::wide::{myers_ed_single_u128, myers_ed_single_wide};
+use myers_ed::word::Word;
+
+use crate::common::{Rng, check_ed};
+
+fn single<T: Word>(a: &[u8], b: &[u8]) -> usize {
+    myers_ed_single_with_peq(&SingleWordPeq::<T>::from_bytes(a), b)
+}
+
+fn compact<T: Word>(a: &[u8], b: &[u8]) -> usize {
+    myers_ed_single_with_peq(&CompactPeq::<T>::from_bytes(a), b)
+}
+
+#[test]
+fn generic_u64_matches_naive() {
+    check_ed(64, single::<u64>);
+    check_ed(64, compact::<u64>);
+}
+
+#[test]
+fn generic_u128_matches_naive() {
+    check_ed(128, single::<u128>);
+    check_ed(128, compact::<u128>);
+    check_ed(128, myers_ed_single_u128);
+}
+
+#[test]
+fn generic_u64_array_matches_naive() {
+    check_ed(128, single::<[u64; 2]>);
+    check_ed(512, single::<[u64; 8]>);
+    check_ed(512, compact::<[u64; 8]>);
+    check_ed(192, myers_ed_single_wide::<3>);
+}
+
+#[test]
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
+fn generic_m512i_matches_naive() {
+    check_ed(512, single::<__m512i>);
+    check_ed(512, compact::<__m512i>);
+}
+
+// Check `shr_unchecked` against repeated `shr_one` for every shift on random words.
+fn check_shr<T: Word>() {
+    let w = 8 * size_of::<T>();
+    let mut rng = Rng::new(w as u64);
+
+    for _ in 0..20 {
+        // Safety: `i < w`.
+        let x = (0..w)
+            .filter(|_| rng.below(2) == 1)
+            .fold(T::ZERO, |x, i| x.bit_or(unsafe { T::bit_at_unchecked(i) }));
+        let mut y = x;
+
+        for n in 0..w {
+            // Safety: `n < w`.
+            let diff = unsafe { x.shr_unchecked(n) }.bit_xor(y);
+            assert_eq!(unsafe { diff.popcnt_upto_unchecked(w) }, 0, "n = {n}");
+
+            y = y.shr_one();
+        }
+    }
+}
+
+#[test]
+fn generic_shr_matches_shr_one() {
+    check_shr::<u64>();
+    check_shr::<u128>();
+    check_shr::<[u64; 3]>();
+    #[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
+    check_shr::<__m512i>();
+}
@@ -0,0 +1,20 @@
+#![cfg_attr(feature = "portable-simd", feature(portable_simd))]
+
+mod common;
+
+mod align;
+mod banded;
+#[cfg(feature = "avx512")]
+mod batch;
+mod dispatch;
+#[cfg(feature = "avx512-emulated")]
+mod emulated;
+mod generic;
+mod multi;
+mod peq;
+#[cfg(feature = "avx512")]
+mod plumbing;
+#[cfg(feature = "portable-simd")]
+mod portable;
+mod search;
+mod single;
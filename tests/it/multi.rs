@@ -0,0 +1,32 @@
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
+use myers_ed::avx512::multi::{myers_ed_multi_avx512, myers_ed_multi_avx512_bounded_with_peq};
+use myers_ed::peq::MultiWordPeq;
+use myers_ed::scalar::multi::{myers_ed_multi_scalar, myers_ed_multi_scalar_bounded_with_peq};
+
+use crate::common::{check_bounded, check_ed};
+
+#[test]
+fn multi_scalar_matches_naive() {
+    check_ed(usize::MAX, myers_ed_multi_scalar);
+}
+
+#[test]
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
+fn multi_avx512_matches_naive() {
+    check_ed(usize::MAX, myers_ed_multi_avx512);
+}
+
+#[test]
+fn multi_scalar_bounded_matches_naive() {
+    check_bounded(usize::MAX, usize::MAX, |a, b, k| {
+        myers_ed_multi_scalar_bounded_with_peq(&MultiWordPeq::from_bytes(a), b, k)
+    });
+}
+
+#[test]
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
+fn multi_avx512_bounded_matches_naive() {
+    check_bounded(usize::MAX, usize::MAX, |a, b, k| {
+        myers_ed_multi_avx512_bounded_with_peq(&MultiWordPeq::from_bytes(a), b, k)
+    });
+}
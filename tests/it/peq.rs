@@ -0,0 +1,16 @@
+use myers_ed::peq::PeqBuilder;
+
+#[test]
+fn peq_builder_new_builds_from_string() {
+    let peq = PeqBuilder::<true, false>::new(Some(b"GATTACA".to_vec()), None).build_single::<u64>();
+
+    assert_eq!(peq.len(), 7);
+}
+
+// The flags are only a claim, so a builder that claims a string it does not hold must panic
+// rather than read a string that is not there.
+#[test]
+#[should_panic(expected = "STRING_CHECK set without a string")]
+fn peq_builder_without_string_panics() {
+    PeqBuilder::<true, false>::default().build_single::<u64>();
+}
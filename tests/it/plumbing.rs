@@ -0,0 +1,80 @@
+use core::arch::x86_64::*;
+
+use myers_ed::avx512::plumbing::{
+    _mm512_add_si512_custom, _mm512_mask_upto_si512_custom, _mm512_slli_si512_custom,
+};
+
+// Lane 0 holds the least significant 64 bits, so carries and shifted out bits move from lane i
+// into lane i + 1.
+fn from_lanes(a: [u64; 8]) -> __m512i {
+    unsafe { core::mem::transmute(a) }
+}
+
+fn lanes(a: __m512i) -> [u64; 8] {
+    unsafe { core::mem::transmute(a) }
+}
+
+#[test]
+fn add_carries_across_lanes() {
+    let a = from_lanes([u64::MAX, u64::MAX, u64::MAX, 0, 0, 0, 0, 0]);
+    let b = from_lanes([1, 0, 0, 0, 0, 0, 0, 0]);
+
+    // The carry out of lane 0 ripples through lanes 1 and 2, past the single likely round.
+    assert_eq!(
+        lanes(_mm512_add_si512_custom::<1>(a, b)),
+        [0, 0, 0, 1, 0, 0, 0, 0]
+    );
+    assert_eq!(
+        lanes(_mm512_add_si512_custom::<8>(a, b)),
+        [0, 0, 0, 1, 0, 0, 0, 0]
+    );
+
+    // Independent carries out of lanes 1 and 5, and the carry out of lane 7 is dropped.
+    let a = from_lanes([3, u64::MAX, 0, 0, 0, 1 << 63, 0, u64::MAX]);
+    let b = from_lanes([4, 2, 0, 0, 0, 1 << 63, 0, 1]);
+
+    assert_eq!(
+        lanes(_mm512_add_si512_custom::<1>(a, b)),
+        [7, 1, 1, 0, 0, 0, 1, 0]
+    );
+}
+
+#[test]
+fn slli_shifts_across_lanes() {
+    let a = from_lanes([1 << 63, 1 << 61, 0, 0, 0, 0, 0, 1 << 63]);
+
+    assert_eq!(
+        lanes(_mm512_slli_si512_custom::<3>(a)),
+        [0, 4, 1, 0, 0, 0, 0, 0]
+    );
+    assert_eq!(
+        lanes(_mm512_slli_si512_custom::<1>(a)),
+        [0, 1 << 62 | 1, 0, 0, 0, 0, 0, 0]
+    );
+}
+
+#[test]
+fn mask_upto_covers_lanes() {
+    // Safety: every `i` is at most 512.
+    unsafe {
+        assert_eq!(lanes(_mm512_mask_upto_si512_custom(0)), [0; 8]);
+        assert_eq!(
+            lanes(_mm512_mask_upto_si512_custom(65)),
+            [u64::MAX, 1, 0, 0, 0, 0, 0, 0]
+        );
+        assert_eq!(
+            lanes(_mm512_mask_upto_si512_custom(448)),
+            [
+                u64::MAX,
+                u64::MAX,
+                u64::MAX,
+                u64::MAX,
+                u64::MAX,
+                u64::MAX,
+                u64::MAX,
+                0
+            ]
+        );
+        assert_eq!(lanes(_mm512_mask_upto_si512_custom(512)), [u64::MAX; 8]);
+    }
+}
@@ -0,0 +1,50 @@
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
+use core::arch::x86_64::__m512i;
+use std::simd::Simd;
+
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
+use myers_ed::avx512::plumbing::{_mm512_add_si512_custom, _mm512_slli_si512_custom};
+use myers_ed::peq::SingleWordPeq;
+use myers_ed::portable::single::{myers_ed_single_portable, myers_ed_single_portable_with_peq};
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
+use myers_ed::word::Word;
+
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
+use crate::common::Rng;
+use crate::common::check_ed;
+
+#[test]
+fn single_portable_matches_naive() {
+    check_ed(128, myers_ed_single_portable::<2>);
+    check_ed(256, myers_ed_single_portable::<4>);
+    check_ed(512, myers_ed_single_portable::<8>);
+}
+
+#[test]
+fn single_portable_with_peq_matches_naive() {
+    check_ed(512, |a, b| {
+        myers_ed_single_portable_with_peq(&SingleWordPeq::<Simd<u64, 8>>::from_bytes(a), b)
+    });
+}
+
+#[test]
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
+fn portable_word_matches_avx512_plumbing() {
+    let mut rng = Rng::new(12);
+
+    for _ in 0..10_000 {
+        let (a, b) = (Simd::from_array(rng.lanes()), Simd::from_array(rng.lanes()));
+        let (ma, mb) = (__m512i::from(a), __m512i::from(b));
+
+        assert_eq!(
+            a.wide_add(b),
+            Simd::from(_mm512_add_si512_custom::<1>(ma, mb)),
+            "a = {a:x?}, b = {b:x?}"
+        );
+
+        let shl = Simd::from(_mm512_slli_si512_custom::<1>(ma));
+
+        assert_eq!(a.shl_one(false), shl, "a = {a:x?}");
+        assert_eq!(a.shl_one(true), shl | Simd::<u64, 8>::ONE, "a = {a:x?}");
+    }
+}
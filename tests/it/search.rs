@@ -0,0 +1,57 @@
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
+use core::arch::x86_64::__m512i;
+
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
+use myers_ed::avx512::search::myers_ed_search_avx512_with_peq;
+use myers_ed::peq::SingleWordPeq;
+use myers_ed::scalar::search::myers_ed_search_scalar_with_peq;
+
+use crate::common::{Rng, naive_last_row, pairs};
+
+// Check `f` against the last row of a DP matrix with a free start, on every pair from `pairs`,
+// and on patterns planted a few edits away in the middle of a random text.
+fn check_search(max_a: usize, f: impl Fn(&[u8], &[u8], usize) -> Vec<(usize, usize)>) {
+    let mut rng = Rng::new(23);
+    let mut cases = pairs(max_a);
+
+    for (a, _) in pairs(max_a) {
+        let text = [rng.bytes(100), rng.mutate(&a, 3), rng.bytes(100)].concat();
+        cases.push((a, text));
+    }
+
+    for (a, text) in cases {
+        let row = naive_last_row(&a, &text, true);
+
+        for k in [0, 1, 3, 10, a.len()] {
+            let hits: Vec<_> = row
+                .iter()
+                .copied()
+                .enumerate()
+                .filter(|&(_, d)| d <= k)
+                .collect();
+
+            assert_eq!(
+                f(&a, &text, k),
+                hits,
+                "a.len() = {}, text.len() = {}, k = {k}",
+                a.len(),
+                text.len()
+            );
+        }
+    }
+}
+
+#[test]
+fn search_scalar_matches_naive() {
+    check_search(64, |a, text, k| {
+        myers_ed_search_scalar_with_peq(&SingleWordPeq::<u64>::from_bytes(a), text, k).collect()
+    });
+}
+
+#[test]
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
+fn search_avx512_matches_naive() {
+    check_search(512, |a, text, k| {
+        myers_ed_search_avx512_with_peq(&SingleWordPeq::<__m512i>::from_bytes(a), text, k).collect()
+    });
+}
@@ -0,0 +1,103 @@
+use myers_ed::mode::Mode;
+use myers_ed::peq::SingleWordPeq;
+use myers_ed::scalar::single::{
+    myers_ed_single_scalar, myers_ed_single_scalar_bounded_with_peq,
+    myers_ed_single_scalar_with_mode,
+};
+
+use crate::common::{Rng, check_bounded, naive_with_mode, pairs};
+
+// Check `f` against `naive_with_mode` in both modes, on every pair from `pairs`. In prefix mode,
+// the text also gets a random tail, so that the best end is usually before the end of the text.
+fn check_mode(max_a: usize, f: impl Fn(&[u8], &[u8], Mode) -> (usize, usize)) {
+    let mut rng = Rng::new(24);
+
+    for (a, b) in pairs(max_a) {
+        let tail = [b.as_slice(), &rng.bytes(50)].concat();
+
+        for (mode, b) in [
+            (Mode::Global, &b),
+            (Mode::Prefix, &b),
+            (Mode::Prefix, &tail),
+        ] {
+            assert_eq!(
+                f(&a, b, mode),
+                naive_with_mode(&a, b, mode),
+                "a.len() = {}, b.len() = {}, mode = {mode:?}",
+                a.len(),
+                b.len()
+            );
+        }
+    }
+}
+
+// Rows past the end of a short pattern keep their initial `vp` bits set, so `(eq & vp) + vp`
+// overflows the word in most columns. The carry out of the top bit must be dropped, not saturate
+// the whole sum.
+#[test]
+fn single_scalar_add_wraps() {
+    assert_eq!(myers_ed_single_scalar(b"CA", b"GAC"), 2);
+}
+
+// A 64-byte pattern fills the word, so the mask of real bits must not be built with `1 << 64`.
+#[test]
+fn single_scalar_full_word_pattern() {
+    assert_eq!(myers_ed_single_scalar(&[b'A'; 64], &[b'C'; 64]), 64);
+    assert_eq!(myers_ed_single_scalar(&[b'A'; 64], &[b'A'; 64]), 0);
+    assert_eq!(myers_ed_single_scalar(&[b'A'; 64], b""), 64);
+}
+
+#[test]
+#[cfg(feature = "avx512-epi32")]
+fn single_avx512_epi32_matches_naive() {
+    use myers_ed::avx512::single::myers_ed_single_avx512_epi32;
+
+    crate::common::check_ed(512, myers_ed_single_avx512_epi32);
+}
+
+#[test]
+#[cfg(feature = "avx2")]
+fn single_avx2_matches_naive() {
+    use myers_ed::avx2::single::myers_ed_single_avx2;
+
+    crate::common::check_ed(256, myers_ed_single_avx2);
+}
+
+#[test]
+#[cfg(feature = "sse")]
+fn single_sse_matches_naive() {
+    use myers_ed::sse::single::myers_ed_single_sse;
+
+    crate::common::check_ed(128, myers_ed_single_sse);
+}
+
+#[test]
+fn single_scalar_bounded_matches_naive() {
+    check_bounded(64, usize::MAX, |a, b, k| {
+        myers_ed_single_scalar_bounded_with_peq(&SingleWordPeq::<u64>::from_bytes(a), b, k)
+    });
+}
+
+#[test]
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
+fn single_avx512_bounded_matches_naive() {
+    use core::arch::x86_64::__m512i;
+    use myers_ed::avx512::single::myers_ed_single_avx512_bounded_with_peq;
+
+    check_bounded(512, usize::MAX, |a, b, k| {
+        myers_ed_single_avx512_bounded_with_peq(&SingleWordPeq::<__m512i>::from_bytes(a), b, k)
+    });
+}
+
+#[test]
+fn single_scalar_with_mode_matches_naive() {
+    check_mode(64, myers_ed_single_scalar_with_mode);
+}
+
+#[test]
+#[cfg(any(feature = "avx512", feature = "avx512-emulated"))]
+fn single_avx512_with_mode_matches_naive() {
+    use myers_ed::avx512::single::myers_ed_single_avx512_with_mode;
+
+    check_mode(512, myers_ed_single_avx512_with_mode);
+}